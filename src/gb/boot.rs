@@ -0,0 +1,53 @@
+use std::fmt;
+
+const DMG_SIZE: usize = 0x100;
+const CGB_SIZE: usize = 0x900;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BootRomError {
+    // Boot ROMs are either 0x100 bytes (DMG, MGB, SGB) or 0x900 bytes (CGB).
+    Size(usize),
+}
+
+impl fmt::Display for BootRomError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BootRomError::Size(size) => {
+                write!(f, "boot ROM is {size:#X} bytes, expected 0x100 or 0x900")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BootRomError {}
+
+/* The boot ROM shadows the start of the cartridge ROM until the program
+ * writes to 0xFF50. The CGB one is longer and leaves 0x100-0x1FF alone so
+ * the boot code can read the cartridge header through the gap.
+ */
+#[derive(Debug)]
+pub struct BootRom {
+    data: Vec<u8>,
+}
+
+impl BootRom {
+    pub fn new(data: Vec<u8>) -> Result<BootRom, BootRomError> {
+        match data.len() {
+            DMG_SIZE | CGB_SIZE => Ok(BootRom { data }),
+            size => Err(BootRomError::Size(size)),
+        }
+    }
+
+    /* Whether the boot ROM covers `address` rather than the cartridge. */
+    pub fn maps(&self, address: u16) -> bool {
+        match address as usize {
+            0x000..=0x0FF => true,
+            0x100..=0x1FF => false,
+            address => address < self.data.len(),
+        }
+    }
+
+    pub fn read(&self, address: u16) -> u8 {
+        self.data[address as usize]
+    }
+}
@@ -0,0 +1,81 @@
+use crate::gb::interrupts::InterruptKind;
+
+// Addresses of the interrupt flag (IF) and interrupt enable (IE) registers.
+pub const IF_ADDRESS: u16 = 0xFF0F;
+pub const IE_ADDRESS: u16 = 0xFFFF;
+
+/* Everything the CPU needs from the system it is plugged into. The CPU owns
+ * the timing: it calls `tick` once per M-cycle, right after the read or
+ * write made in that cycle, if any.
+ */
+pub trait Bus {
+    fn read(&mut self, address: u16) -> u8;
+
+    fn write(&mut self, address: u16, val: u8);
+
+    /* Little-endian word access, wrapping from 0xFFFF to 0x0000. These make
+     * two accesses without ticking in between; the CPU uses its own word
+     * helpers instead so each byte gets its own M-cycle.
+     */
+    fn read_word(&mut self, address: u16) -> u16 {
+        let low = self.read(address);
+        let high = self.read(address.wrapping_add(1));
+        (high as u16) << 8 | low as u16
+    }
+
+    fn write_word(&mut self, address: u16, val: u16) {
+        self.write(address, (val & 0x00FF) as u8);
+        self.write(address.wrapping_add(1), (val >> 8) as u8);
+    }
+
+    /* Advances the rest of the system by one M-cycle. */
+    fn tick(&mut self);
+
+    /* Interrupts that are both requested (IF) and enabled (IE). The CPU
+     * checks these outside of its bus cycles, so implementations that track
+     * accesses may want to override this with a side-effect free version.
+     */
+    fn pending_interrupts(&mut self) -> u8 {
+        self.read(IF_ADDRESS) & self.read(IE_ADDRESS) & 0x1F
+    }
+
+    /* Clears the IF bit of an interrupt the CPU has started servicing. */
+    fn acknowledge_interrupt(&mut self, kind: InterruptKind) {
+        let requested = self.read(IF_ADDRESS);
+        self.write(IF_ADDRESS, requested & !kind.mask());
+    }
+}
+
+/* A bare 64 KiB of RAM with nothing mapped over it, for running the CPU on
+ * its own in test harnesses, fuzzers or non-Game Boy SM83 experiments.
+ */
+#[derive(Debug)]
+pub struct FlatRam {
+    memory: Box<[u8; 0x10000]>,
+}
+
+impl Default for FlatRam {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FlatRam {
+    pub fn new() -> FlatRam {
+        FlatRam {
+            memory: Box::new([0; 0x10000]),
+        }
+    }
+}
+
+impl Bus for FlatRam {
+    fn read(&mut self, address: u16) -> u8 {
+        self.memory[address as usize]
+    }
+
+    fn write(&mut self, address: u16, val: u8) {
+        self.memory[address as usize] = val;
+    }
+
+    fn tick(&mut self) {}
+}
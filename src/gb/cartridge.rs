@@ -0,0 +1,348 @@
+use crate::gb::mbc::{Clock, HuC1, HuC3, Mbc1, Mbc2, Mbc3, Mbc5, Mbc7, MemoryBankController};
+use crate::gb::mbc::{PocketCamera, RomOnly, SystemClock};
+use std::fmt;
+
+// The header sits at 0x0100-0x014F, so anything shorter can't be a ROM.
+const HEADER_END: usize = 0x0150;
+
+// Compared against the cartridge by the boot ROM, which locks up on a mismatch.
+pub const NINTENDO_LOGO: [u8; 48] = [
+    0xCE, 0xED, 0x66, 0x66, 0xCC, 0x0D, 0x00, 0x0B, 0x03, 0x73, 0x00, 0x83,
+    0x00, 0x0C, 0x00, 0x0D, 0x00, 0x08, 0x11, 0x1F, 0x88, 0x89, 0x00, 0x0E,
+    0xDC, 0xCC, 0x6E, 0xE6, 0xDD, 0xDD, 0xD9, 0x99, 0xBB, 0xBB, 0x67, 0x63,
+    0x6E, 0x0E, 0xEC, 0xCC, 0xDD, 0xDC, 0x99, 0x9F, 0xBB, 0xB9, 0x33, 0x3E,
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CartridgeError {
+    // The image is too short to hold a header.
+    Truncated(usize),
+    UnknownType(u8),
+    UnknownRomSize(u8),
+    UnknownRamSize(u8),
+    // The header's ROM size doesn't match the size of the image.
+    RomSizeMismatch { header: usize, actual: usize },
+    Logo,
+    HeaderChecksum { header: u8, actual: u8 },
+    GlobalChecksum { header: u16, actual: u16 },
+    // The header is fine, but we don't emulate its controller yet.
+    Unsupported(Controller),
+}
+
+impl fmt::Display for CartridgeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CartridgeError::Truncated(size) => {
+                write!(f, "ROM is only {size:#X} bytes, too short for a header")
+            }
+            CartridgeError::UnknownType(code) => write!(f, "unknown cartridge type {code:#04X}"),
+            CartridgeError::UnknownRomSize(code) => write!(f, "unknown ROM size {code:#04X}"),
+            CartridgeError::UnknownRamSize(code) => write!(f, "unknown RAM size {code:#04X}"),
+            CartridgeError::RomSizeMismatch { header, actual } => {
+                write!(f, "header says the ROM is {header:#X} bytes, but it is {actual:#X}")
+            }
+            CartridgeError::Logo => write!(f, "Nintendo logo does not match"),
+            CartridgeError::HeaderChecksum { header, actual } => {
+                write!(f, "header checksum is {actual:#04X}, header says {header:#04X}")
+            }
+            CartridgeError::GlobalChecksum { header, actual } => {
+                write!(f, "global checksum is {actual:#06X}, header says {header:#06X}")
+            }
+            CartridgeError::Unsupported(controller) => {
+                write!(f, "{controller:?} cartridges are not supported yet")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CartridgeError {}
+
+/* The chip that maps the ROM and RAM into the address space. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Controller {
+    RomOnly,
+    Mbc1,
+    Mbc2,
+    Mmm01,
+    Mbc3,
+    Mbc5,
+    Mbc6,
+    Mbc7,
+    PocketCamera,
+    Tama5,
+    HuC3,
+    HuC1,
+}
+
+/* Byte 0x0147: the controller plus whatever else is on the board. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CartridgeType {
+    pub code: u8,
+    pub controller: Controller,
+    pub ram: bool,
+    pub battery: bool,
+    pub timer: bool,
+    pub rumble: bool,
+    pub sensor: bool,
+}
+
+impl TryFrom<u8> for CartridgeType {
+    type Error = CartridgeError;
+
+    fn try_from(code: u8) -> Result<Self, Self::Error> {
+        use Controller::*;
+        let (controller, ram, battery, timer, rumble, sensor) = match code {
+            0x00 => (RomOnly, false, false, false, false, false),
+            0x01 => (Mbc1, false, false, false, false, false),
+            0x02 => (Mbc1, true, false, false, false, false),
+            0x03 => (Mbc1, true, true, false, false, false),
+            0x05 => (Mbc2, false, false, false, false, false),
+            0x06 => (Mbc2, false, true, false, false, false),
+            0x08 => (RomOnly, true, false, false, false, false),
+            0x09 => (RomOnly, true, true, false, false, false),
+            0x0B => (Mmm01, false, false, false, false, false),
+            0x0C => (Mmm01, true, false, false, false, false),
+            0x0D => (Mmm01, true, true, false, false, false),
+            0x0F => (Mbc3, false, true, true, false, false),
+            0x10 => (Mbc3, true, true, true, false, false),
+            0x11 => (Mbc3, false, false, false, false, false),
+            0x12 => (Mbc3, true, false, false, false, false),
+            0x13 => (Mbc3, true, true, false, false, false),
+            0x19 => (Mbc5, false, false, false, false, false),
+            0x1A => (Mbc5, true, false, false, false, false),
+            0x1B => (Mbc5, true, true, false, false, false),
+            0x1C => (Mbc5, false, false, false, true, false),
+            0x1D => (Mbc5, true, false, false, true, false),
+            0x1E => (Mbc5, true, true, false, true, false),
+            0x20 => (Mbc6, true, true, false, false, false),
+            0x22 => (Mbc7, true, true, false, true, true),
+            0xFC => (PocketCamera, true, true, false, false, false),
+            0xFD => (Tama5, true, true, true, false, false),
+            0xFE => (HuC3, true, true, true, false, false),
+            0xFF => (HuC1, true, true, false, false, false),
+            _ => return Err(CartridgeError::UnknownType(code)),
+        };
+        Ok(CartridgeType {
+            code,
+            controller,
+            ram,
+            battery,
+            timer,
+            rumble,
+            sensor,
+        })
+    }
+}
+
+/* Byte 0x0143. Only the top bit matters to hardware. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CgbSupport {
+    None,
+    Compatible,
+    Only,
+}
+
+/* Older games have a one byte publisher code at 0x014B. Newer ones set it to
+ * 0x33 and use two ASCII characters at 0x0144-0x0145 instead.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Licensee {
+    Old(u8),
+    New(String),
+}
+
+/* A ROM image and what its header at 0x0100-0x014F says about it. */
+#[derive(Debug)]
+pub struct Cartridge {
+    pub title: String,
+    pub manufacturer: Option<String>,
+    pub cgb: CgbSupport,
+    pub sgb: bool,
+    pub licensee: Licensee,
+    pub cartridge_type: CartridgeType,
+    pub rom_size: usize,
+    pub ram_size: usize,
+    pub japanese: bool,
+    pub version: u8,
+    pub header_checksum: u8,
+    pub global_checksum: u16,
+    rom: Vec<u8>,
+}
+
+impl Cartridge {
+    /* Parses the header. This only fails if the header can't be made sense
+     * of; see `verify` for the checks the boot ROM and tooling do.
+     */
+    pub fn new(rom: Vec<u8>) -> Result<Cartridge, CartridgeError> {
+        if rom.len() < HEADER_END {
+            return Err(CartridgeError::Truncated(rom.len()));
+        }
+
+        let cgb = match rom[0x0143] {
+            0xC0 => CgbSupport::Only,
+            flag if flag & 0x80 != 0 => CgbSupport::Compatible,
+            _ => CgbSupport::None,
+        };
+        // Later titles are cut short by the CGB flag and sometimes a four
+        // letter manufacturer code. The code is only there if it looks
+        // like one, since older titles use the same bytes for text.
+        let (title, manufacturer) = if cgb == CgbSupport::None {
+            (ascii(&rom[0x0134..0x0144]), None)
+        } else {
+            let code = &rom[0x013F..0x0143];
+            if code.iter().all(|byte| byte.is_ascii_uppercase() || byte.is_ascii_digit()) {
+                (ascii(&rom[0x0134..0x013F]), Some(ascii(code)))
+            } else {
+                (ascii(&rom[0x0134..0x0143]), None)
+            }
+        };
+
+        let licensee = match rom[0x014B] {
+            0x33 => Licensee::New(ascii(&rom[0x0144..0x0146])),
+            code => Licensee::Old(code),
+        };
+
+        let cartridge_type = CartridgeType::try_from(rom[0x0147])?;
+        let rom_size = match rom[0x0148] {
+            code @ 0x00..=0x08 => 0x8000 << code,
+            // Only seen in docs, never in a real game.
+            0x52 => 72 * 0x4000,
+            0x53 => 80 * 0x4000,
+            0x54 => 96 * 0x4000,
+            code => return Err(CartridgeError::UnknownRomSize(code)),
+        };
+        let ram_size = match rom[0x0149] {
+            0x00 => 0,
+            0x01 => 0x800,
+            0x02 => 0x2000,
+            0x03 => 0x8000,
+            0x04 => 0x20000,
+            0x05 => 0x10000,
+            code => return Err(CartridgeError::UnknownRamSize(code)),
+        };
+        if rom.len() != rom_size {
+            return Err(CartridgeError::RomSizeMismatch {
+                header: rom_size,
+                actual: rom.len(),
+            });
+        }
+
+        Ok(Cartridge {
+            title,
+            manufacturer,
+            cgb,
+            sgb: rom[0x0146] == 0x03,
+            licensee,
+            cartridge_type,
+            rom_size,
+            ram_size,
+            japanese: rom[0x014A] == 0x00,
+            version: rom[0x014C],
+            header_checksum: rom[0x014D],
+            global_checksum: (rom[0x014E] as u16) << 8 | rom[0x014F] as u16,
+            rom,
+        })
+    }
+
+    /* Checks the logo, header checksum and global checksum, in that order.
+     * Real hardware only cares about the first two, so a bad global
+     * checksum is common in homebrew and harmless.
+     */
+    pub fn verify(&self) -> Result<(), CartridgeError> {
+        if self.rom[0x0104..0x0134] != NINTENDO_LOGO {
+            return Err(CartridgeError::Logo);
+        }
+        let actual = self.compute_header_checksum();
+        if actual != self.header_checksum {
+            return Err(CartridgeError::HeaderChecksum {
+                header: self.header_checksum,
+                actual,
+            });
+        }
+        let actual = self.compute_global_checksum();
+        if actual != self.global_checksum {
+            return Err(CartridgeError::GlobalChecksum {
+                header: self.global_checksum,
+                actual,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn compute_header_checksum(&self) -> u8 {
+        self.rom[0x0134..0x014D]
+            .iter()
+            .fold(0u8, |sum, byte| sum.wrapping_sub(*byte).wrapping_sub(1))
+    }
+
+    /* Sum of every byte in the ROM except the checksum itself. */
+    pub fn compute_global_checksum(&self) -> u16 {
+        self.rom
+            .iter()
+            .enumerate()
+            .filter(|(pos, _)| !matches!(pos, 0x014E | 0x014F))
+            .fold(0u16, |sum, (_, byte)| sum.wrapping_add(*byte as u16))
+    }
+
+    pub fn rom(&self) -> &[u8] {
+        &self.rom
+    }
+
+    /* Some headers list a RAM size for boards without any RAM chip. */
+    fn board_ram_size(&self) -> usize {
+        if self.cartridge_type.ram { self.ram_size } else { 0 }
+    }
+
+    /* Builds the memory bank controller this cartridge's header asks for. */
+    pub fn into_controller(self) -> Result<Box<dyn MemoryBankController>, CartridgeError> {
+        match self.cartridge_type.controller {
+            Controller::RomOnly => {
+                let ram_size = self.board_ram_size();
+                Ok(Box::new(RomOnly::with_ram(self.rom, ram_size)))
+            }
+            Controller::Mbc1 => {
+                let ram_size = self.board_ram_size();
+                let multicart = Mbc1::detect_multicart(&self.rom);
+                Ok(Box::new(Mbc1::new(self.rom, ram_size, multicart)))
+            }
+            Controller::Mbc2 => Ok(Box::new(Mbc2::new(self.rom))),
+            Controller::Mbc3 => {
+                let ram_size = self.board_ram_size();
+                let clock: Option<Box<dyn Clock>> = if self.cartridge_type.timer {
+                    Some(Box::new(SystemClock))
+                } else {
+                    None
+                };
+                Ok(Box::new(Mbc3::new(self.rom, ram_size, clock)))
+            }
+            Controller::Mbc5 => {
+                let ram_size = self.board_ram_size();
+                let rumble = self.cartridge_type.rumble;
+                Ok(Box::new(Mbc5::new(self.rom, ram_size, rumble)))
+            }
+            Controller::Mbc7 => Ok(Box::new(Mbc7::new(self.rom))),
+            Controller::HuC1 => {
+                let ram_size = self.board_ram_size();
+                Ok(Box::new(HuC1::new(self.rom, ram_size)))
+            }
+            Controller::HuC3 => {
+                let ram_size = self.board_ram_size();
+                Ok(Box::new(HuC3::new(self.rom, ram_size, Box::new(SystemClock))))
+            }
+            Controller::PocketCamera => Ok(Box::new(PocketCamera::new(self.rom))),
+            controller => Err(CartridgeError::Unsupported(controller)),
+        }
+    }
+}
+
+/* Header text is ASCII padded with zeroes. */
+fn ascii(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .take_while(|byte| **byte != 0x00)
+        .map(|byte| match byte {
+            0x20..=0x7E => *byte as char,
+            _ => '?',
+        })
+        .collect()
+}
@@ -1,42 +1,258 @@
+use crate::gb::bus::Bus;
 use crate::gb::instructions::Instruction as Instr;
 use crate::gb::instructions::{
     B0Instruction as B0Inst, B1Instruction as B1Inst, B2Instruction as B2Inst,
-    B3Instruction as B3Inst, PrefixedInstruction as PrefixedInst,
+    B3Instruction as B3Inst, InstructionError, PrefixedInstruction as PrefixedInst,
 };
+use crate::gb::interrupts::InterruptKind;
 use crate::gb::mmu::MemoryManagementUnit as MMU; // Use the acronym for space.
+use crate::gb::model::Model;
 use crate::gb::registers as reg;
 
+use std::fmt;
+
+// LDH addresses the I/O registers and HRAM at 0xFF00 + an 8-bit offset.
+const HIGH_PAGE: u16 = 0xFF00;
+
+/* What a single call to `CPU::cycle` did and how long it took. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StepInfo {
+    pub m_cycles: u8,
+    pub event: StepEvent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepEvent {
+    // An instruction fetched from `address` was executed.
+    Executed { address: u16, opcode: u8, prefixed: bool },
+    // An interrupt was dispatched. `None` means the dispatch was cancelled by
+    // the push overwriting IE, see `dispatch_interrupt`.
+    Interrupt(Option<InterruptKind>),
+    // The CPU spent the M-cycle halted.
+    Halted,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CpuError {
+    // One of the eleven unused opcodes, which real hardware cannot execute.
+    IllegalOpcode { address: u16, opcode: u8 },
+    // The decoder handed the executor something it cannot run. This is a bug
+    // in crabbyboy rather than in the program being emulated.
+    Decode { address: u16, opcode: u8, prefixed: bool },
+    // The CPU hung on the illegal opcode at `address` earlier and will not
+    // run anything else.
+    Locked { address: u16, opcode: u8 },
+}
+
+impl fmt::Display for CpuError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CpuError::IllegalOpcode { address, opcode } => {
+                write!(f, "illegal opcode {opcode:#04X} at {address:#06X}")
+            }
+            CpuError::Decode {
+                address,
+                opcode,
+                prefixed,
+            } => {
+                let prefix = if *prefixed { "0xCB " } else { "" };
+                write!(f, "could not decode {prefix}{opcode:#04X} at {address:#06X}")
+            }
+            CpuError::Locked { address, opcode } => {
+                write!(f, "locked up by illegal opcode {opcode:#04X} at {address:#06X}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CpuError {}
+
+/* The SM83 core. It is generic over the bus it is wired to, which is the
+ * full Game Boy memory map unless told otherwise.
+ */
 #[derive(Debug)]
-pub struct CPU {
-    registers: reg::Registers,
-    pub memory_bus: MMU,
+pub struct CPU<B: Bus = MMU> {
+    pub registers: reg::Registers,
+    pub memory_bus: B,
     pub end: bool,
+    pub ime: bool,
+    // M-cycles spent by the instruction in progress and by all of them so far.
+    step_cycles: u8,
+    cycles: u64,
+    // Instructions left until a pending EI takes effect, see `ei`.
+    ime_delay: u8,
+    halted: bool,
+    halt_bug: bool,
+    // Set by an illegal opcode. Only a reset gets the CPU running again.
+    locked: bool,
+    // The instruction being executed, kept around for error reporting.
+    current: (u16, u8, bool),
+}
+
+impl Default for CPU {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl CPU {
     pub fn new() -> CPU {
+        CPU::with_bus(MMU::new())
+    }
+
+    /* Puts the CPU and I/O registers in the state `model`'s boot ROM leaves
+     * them in, ready to run the cartridge from 0x0100 without a boot ROM.
+     * The cartridge should already be inserted, since the DMG flags depend
+     * on its header checksum.
+     */
+    pub fn skip_boot(&mut self, model: Model) {
+        let header_checksum = self.memory_bus.read_byte(0x014D);
+        self.registers = model.boot_registers(header_checksum);
+        for (address, val) in model.boot_io() {
+            self.memory_bus.set_byte(address, val);
+        }
+        self.memory_bus.interrupts.write_enable(0x00);
+        self.memory_bus.timer.set_counter(model.boot_div());
+        self.ime = false;
+    }
+}
+
+impl<B: Bus> CPU<B> {
+    pub fn with_bus(memory_bus: B) -> CPU<B> {
         CPU {
             registers: reg::Registers::new(),
-            memory_bus: MMU::new(),
+            memory_bus,
             end: false,
+            ime: false,
+            step_cycles: 0,
+            cycles: 0,
+            ime_delay: 0,
+            halted: false,
+            halt_bug: false,
+            locked: false,
+            current: (0x0000, 0x00, false),
         }
     }
 
-    pub fn cycle(&mut self) {
+    /* Runs one instruction, interrupt dispatch or halted M-cycle and reports
+     * what happened and how many M-cycles it took.
+     */
+    pub fn cycle(&mut self) -> Result<StepInfo, CpuError> {
+        self.step_cycles = 0;
+        let result = self.step();
+        self.cycles += self.step_cycles as u64;
+        Ok(StepInfo {
+            m_cycles: self.step_cycles,
+            event: result?,
+        })
+    }
+
+    /* Total M-cycles run since the CPU was created. */
+    pub fn total_cycles(&self) -> u64 {
+        self.cycles
+    }
+
+    /* True once an illegal opcode has hung the CPU. */
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    fn step(&mut self) -> Result<StepEvent, CpuError> {
+        // A locked CPU stops fetching and ignores interrupts, but the clock
+        // keeps running so the rest of the system carries on around it.
+        if self.locked {
+            self.idle();
+            let (address, opcode, _) = self.current;
+            return Err(CpuError::Locked { address, opcode });
+        }
+
+        if self.halted {
+            // HALT only ends once an enabled interrupt is requested, whether
+            // or not IME allows it to be serviced.
+            if self.pending_interrupts() == 0 {
+                self.idle();
+                return Ok(StepEvent::Halted);
+            }
+            self.halted = false;
+        }
+
+        if self.ime && self.pending_interrupts() != 0 {
+            let kind = self.dispatch_interrupt();
+            return Ok(StepEvent::Interrupt(kind));
+        }
+
+        let address = self.registers.pc;
         let mut byte = self.fetch();
         let prefixed: bool = byte == 0xCB;
         if prefixed {
             byte = self.fetch();
         }
-        self.execute(byte, prefixed);
+        self.current = (address, byte, prefixed);
+        self.execute(byte, prefixed)?;
+
+        if self.ime_delay > 0 {
+            self.ime_delay -= 1;
+            if self.ime_delay == 0 {
+                self.ime = true;
+            }
+        }
+        Ok(StepEvent::Executed {
+            address,
+            opcode: byte,
+            prefixed,
+        })
+    }
+
+    /* Services the highest priority pending interrupt. This takes 5 M-cycles
+     * on hardware: two idle cycles, two to push PC and one to jump.
+     * Pushing the high byte of PC can overwrite IE when SP is 0x0000, so the
+     * interrupt to service is only picked after that write. If nothing is
+     * left pending by then, the dispatch is cancelled and PC becomes 0x0000.
+     * After `EI; HALT` the HALT bug is still armed when the interrupt comes
+     * in. Instead of repeating a byte, the return address is HALT itself, so
+     * it runs again once the handler returns.
+     */
+    fn dispatch_interrupt(&mut self) -> Option<InterruptKind> {
+        self.ime = false;
+        self.idle();
+        self.idle();
+        let mut pc = self.registers.pc;
+        if self.halt_bug {
+            self.halt_bug = false;
+            pc = pc.wrapping_sub(1);
+        }
+
+        self.registers.sp = self.registers.sp.wrapping_sub(1);
+        self.write_byte(self.registers.sp, (pc >> 8) as u8);
+
+        let kind = InterruptKind::highest(self.pending_interrupts());
+
+        self.registers.sp = self.registers.sp.wrapping_sub(1);
+        self.write_byte(self.registers.sp, (pc & 0x00FF) as u8);
+
+        match kind {
+            Some(kind) => {
+                self.memory_bus.acknowledge_interrupt(kind);
+                self.registers.pc = kind.vector();
+            }
+            None => self.registers.pc = 0x0000,
+        }
+        self.idle();
+        kind
     }
 
     /* Grabs and returns one single byte from the address stored at the program
      * counter, then advances the program counter once.
      */
     fn fetch(&mut self) -> u8 {
-        let byte = self.memory_bus.read_byte(self.registers.pc);
-        self.registers.advance_pc();
+        let byte = self.read_byte(self.registers.pc);
+        // The HALT bug makes the CPU fail to increment the PC once, so the
+        // byte after HALT gets read twice.
+        if self.halt_bug {
+            self.halt_bug = false;
+        } else {
+            self.registers.advance_pc();
+        }
         byte
     }
 
@@ -44,16 +260,17 @@ impl CPU {
      * prgram counter, then advances the program counter twice.
      */
     fn fetch_n16(&mut self) -> u16 {
-        let n16: u16 = self.memory_bus.read_word(self.registers.pc);
-        self.registers.advance_pc();
-        self.registers.advance_pc();
+        // The HALT bug only ever affects the opcode fetch, so operands can
+        // skip fetch() and step the PC past both bytes at once.
+        let n16 = self.read_word(self.registers.pc);
+        self.registers.pc = self.registers.pc.wrapping_add(2);
         n16
     }
 
     /* Parent function to execute the an instruction. Filters down through
      * successive match cases to perform the expected instruction.
      */
-    fn execute(&mut self, byte: u8, prefixed: bool) {
+    fn execute(&mut self, byte: u8, prefixed: bool) -> Result<(), CpuError> {
         let instruction_result = Instr::from_byte(byte, prefixed);
         match instruction_result {
             Ok(Instr::Block0(instruction)) => self.execute_block_zero(instruction),
@@ -61,7 +278,12 @@ impl CPU {
             Ok(Instr::Block2(instruction)) => self.execute_block_two(instruction),
             Ok(Instr::Block3(instruction)) => self.execute_block_three(instruction),
             Ok(Instr::Prefixed(instruction)) => self.execute_prefixed(instruction),
-            Err(error) => println!("{error:?}"),
+            Err(InstructionError::Invalid) => {
+                let (address, opcode, _) = self.current;
+                self.locked = true;
+                Err(CpuError::IllegalOpcode { address, opcode })
+            }
+            Err(InstructionError::NotFound) => Err(self.decode_error()),
         }
     }
 
@@ -69,19 +291,19 @@ impl CPU {
      * responsibility to individual helper functions for the specific opcode
      * families.
      */
-    fn execute_block_zero(&mut self, instruction: B0Inst) {
+    fn execute_block_zero(&mut self, instruction: B0Inst) -> Result<(), CpuError> {
         match instruction {
-            B0Inst::NOP => println!("Got NOP"),
-            B0Inst::LDR16N16(dest) => self.ldr16n16(dest),
-            B0Inst::LDR16(dest) => self.ldr16(dest),
-            B0Inst::LDA(source) => self.lda(source),
+            B0Inst::NOP => {}
+            B0Inst::LDR16N16(dest) => self.ldr16n16(dest)?,
+            B0Inst::LDR16(dest) => self.ldr16(dest)?,
+            B0Inst::LDA(source) => self.lda(source)?,
             B0Inst::LDN16SP => self.ldn16sp(),
-            B0Inst::INCR16(operand) => self.incr16(operand),
-            B0Inst::DECR16(operand) => self.decr16(operand),
-            B0Inst::ADDHL(operand) => self.addhl(operand),
-            B0Inst::INCR8(operand) => self.incr8(operand),
-            B0Inst::DECR8(operand) => self.decr8(operand),
-            B0Inst::LDR8N8(dest) => self.ldr8n8(dest),
+            B0Inst::INCR16(operand) => self.incr16(operand)?,
+            B0Inst::DECR16(operand) => self.decr16(operand)?,
+            B0Inst::ADDHL(operand) => self.addhl(operand)?,
+            B0Inst::INCR8(operand) => self.incr8(operand)?,
+            B0Inst::DECR8(operand) => self.decr8(operand)?,
+            B0Inst::LDR8N8(dest) => self.ldr8n8(dest)?,
             B0Inst::RLCA => self.rlca(),
             B0Inst::RRCA => self.rrca(),
             B0Inst::RLA => self.rla(),
@@ -91,106 +313,223 @@ impl CPU {
             B0Inst::SCF => self.scf(),
             B0Inst::CCF => self.ccf(),
             B0Inst::JRN8 => self.jrn8(),
-            B0Inst::JRCONDN8(cond) => self.jrcondn8(cond),
+            B0Inst::JRCONDN8(cond) => self.jrcondn8(cond)?,
             B0Inst::STOP => self.stop(),
         }
+        Ok(())
     }
 
-    fn execute_block_one(&mut self, instruction: B1Inst) {
+    fn execute_block_one(&mut self, instruction: B1Inst) -> Result<(), CpuError> {
         match instruction {
-            B1Inst::LD { dest, source } => println!("GOT LD: {dest} - {source}"),
-            B1Inst::HALT => println!("Got HALT"),
+            B1Inst::LD { dest, source } => self.ldr8r8(dest, source)?,
+            B1Inst::HALT => self.halt(),
         }
+        Ok(())
     }
 
-    fn execute_block_two(&mut self, instruction: B2Inst) {
+    fn execute_block_two(&mut self, instruction: B2Inst) -> Result<(), CpuError> {
+        let val = match instruction {
+            B2Inst::ADD(operand)
+            | B2Inst::ADC(operand)
+            | B2Inst::SUB(operand)
+            | B2Inst::SBC(operand)
+            | B2Inst::AND(operand)
+            | B2Inst::XOR(operand)
+            | B2Inst::OR(operand)
+            | B2Inst::CP(operand) => self.read_r8(operand)?,
+        };
         match instruction {
-            B2Inst::ADD(val) => println!("GOT ADD: {val}"),
-            _ => println!("Idk"),
+            B2Inst::ADD(_) => self.alu_add(val, false),
+            B2Inst::ADC(_) => self.alu_add(val, true),
+            B2Inst::SUB(_) => self.alu_sub(val, false),
+            B2Inst::SBC(_) => self.alu_sub(val, true),
+            B2Inst::AND(_) => self.alu_and(val),
+            B2Inst::XOR(_) => self.alu_xor(val),
+            B2Inst::OR(_) => self.alu_or(val),
+            B2Inst::CP(_) => self.alu_cp(val),
         }
+        Ok(())
     }
 
-    fn execute_block_three(&mut self, instruction: B3Inst) {
+    fn execute_block_three(&mut self, instruction: B3Inst) -> Result<(), CpuError> {
         match instruction {
-            B3Inst::ADDN8 => println!("GOT ADDN8)"),
-            _ => println!("Idk"),
+            B3Inst::ADDN8 => {
+                let n8 = self.fetch();
+                self.alu_add(n8, false);
+            }
+            B3Inst::ADCN8 => {
+                let n8 = self.fetch();
+                self.alu_add(n8, true);
+            }
+            B3Inst::SUBN8 => {
+                let n8 = self.fetch();
+                self.alu_sub(n8, false);
+            }
+            B3Inst::SBCN8 => {
+                let n8 = self.fetch();
+                self.alu_sub(n8, true);
+            }
+            B3Inst::ANDN8 => {
+                let n8 = self.fetch();
+                self.alu_and(n8);
+            }
+            B3Inst::XORN8 => {
+                let n8 = self.fetch();
+                self.alu_xor(n8);
+            }
+            B3Inst::ORN8 => {
+                let n8 = self.fetch();
+                self.alu_or(n8);
+            }
+            B3Inst::CPN8 => {
+                let n8 = self.fetch();
+                self.alu_cp(n8);
+            }
+            B3Inst::RETCOND(cond) => self.retcond(cond)?,
+            B3Inst::RET => self.ret(),
+            B3Inst::RETI => self.reti(),
+            B3Inst::JPCONDN8(cond) => self.jpcondn16(cond)?,
+            B3Inst::JPN16 => self.jpn16(),
+            B3Inst::JPHL => self.registers.pc = self.registers.hl(),
+            B3Inst::CALLCONDN8(cond) => self.callcondn16(cond)?,
+            B3Inst::CALLN16 => self.calln16(),
+            B3Inst::RST(tgt3) => self.rst(tgt3),
+            B3Inst::POP(register) => self.pop(register)?,
+            B3Inst::PUSH(register) => self.push(register)?,
+            B3Inst::ADDSPN8 => self.addspn8(),
+            B3Inst::LDHLSPN8 => self.ldhlspn8(),
+            B3Inst::LDSPHL => {
+                self.registers.sp = self.registers.hl();
+                self.idle();
+            }
+            B3Inst::DI => self.di(),
+            B3Inst::EI => self.ei(),
+            B3Inst::LDHCA => {
+                let address = HIGH_PAGE + self.registers.c as u16;
+                self.write_byte(address, self.registers.a);
+            }
+            B3Inst::LDHAC => {
+                let address = HIGH_PAGE + self.registers.c as u16;
+                self.registers.a = self.read_byte(address);
+            }
+            B3Inst::LDHN8A => {
+                let address = HIGH_PAGE + self.fetch() as u16;
+                self.write_byte(address, self.registers.a);
+            }
+            B3Inst::LDHAN8 => {
+                let address = HIGH_PAGE + self.fetch() as u16;
+                self.registers.a = self.read_byte(address);
+            }
+            B3Inst::LDN16A => {
+                let address = self.fetch_n16();
+                self.write_byte(address, self.registers.a);
+            }
+            B3Inst::LDAN16 => {
+                let address = self.fetch_n16();
+                self.registers.a = self.read_byte(address);
+            }
         }
+        Ok(())
     }
 
-    fn execute_prefixed(&mut self, instruction: PrefixedInst) {
+    fn execute_prefixed(&mut self, instruction: PrefixedInst) -> Result<(), CpuError> {
         match instruction {
-            PrefixedInst::RLC(operand) => println!("Got RLC(operand) - {operand}"),
-            _ => println!("Idk"),
+            PrefixedInst::RLC(operand) => {
+                self.shift(operand, |val, _| (val.rotate_left(1), val >> 7))?
+            }
+            PrefixedInst::RRC(operand) => {
+                self.shift(operand, |val, _| (val.rotate_right(1), val & 1))?
+            }
+            PrefixedInst::RL(operand) => self.shift(operand, |val, c| (val << 1 | c, val >> 7))?,
+            PrefixedInst::RR(operand) => {
+                self.shift(operand, |val, c| (val >> 1 | c << 7, val & 1))?
+            }
+            PrefixedInst::SL(operand) => self.shift(operand, |val, _| (val << 1, val >> 7))?,
+            PrefixedInst::SR(operand) => {
+                self.shift(operand, |val, _| (val >> 1 | (val & 0x80), val & 1))?
+            }
+            PrefixedInst::SWAP(operand) => self.shift(operand, |val, _| (val.rotate_left(4), 0))?,
+            PrefixedInst::SRL(operand) => self.shift(operand, |val, _| (val >> 1, val & 1))?,
+            PrefixedInst::BIT { b3, operand } => self.bit(b3, operand)?,
+            PrefixedInst::RES { b3, operand } => {
+                let val = self.read_r8(operand)?;
+                self.write_r8(operand, val & !(1 << b3))?;
+            }
+            PrefixedInst::SET { b3, operand } => {
+                let val = self.read_r8(operand)?;
+                self.write_r8(operand, val | (1 << b3))?;
+            }
         }
+        Ok(())
     }
 
     // Begin Block 0 Helper Functions
-    fn ldr16n16(&mut self, dest: u8) {
+    fn ldr16n16(&mut self, dest: u8) -> Result<(), CpuError> {
         let n16: u16 = self.fetch_n16();
         match reg::R16::try_from(dest) {
             Ok(reg::R16::BC) => self.registers.set_bc(n16),
             Ok(reg::R16::DE) => self.registers.set_de(n16),
             Ok(reg::R16::HL) => self.registers.set_hl(n16),
             Ok(reg::R16::SP) => self.registers.sp = n16,
-            Err(err) => panic!("{err:?}"),
+            Err(_) => return Err(self.decode_error()),
         }
+        Ok(())
     }
 
-    fn ldr16(&mut self, dest: u8) {
+    fn ldr16(&mut self, dest: u8) -> Result<(), CpuError> {
         match reg::R16Mem::try_from(dest) {
             Ok(reg::R16Mem::BC) => {
-                self.memory_bus
-                    .set_byte(self.registers.bc(), self.registers.a);
+                self.write_byte(self.registers.bc(), self.registers.a);
             }
             Ok(reg::R16Mem::DE) => {
-                self.memory_bus
-                    .set_byte(self.registers.de(), self.registers.a);
+                self.write_byte(self.registers.de(), self.registers.a);
             }
             Ok(reg::R16Mem::HLI) => {
                 let hl = self.registers.hl();
-                self.memory_bus.set_byte(hl, self.registers.a);
+                self.write_byte(hl, self.registers.a);
                 self.registers.set_hl(hl.wrapping_add(1));
             }
             Ok(reg::R16Mem::HLD) => {
                 let hl = self.registers.hl();
-                self.memory_bus.set_byte(hl, self.registers.a);
+                self.write_byte(hl, self.registers.a);
                 self.registers.set_hl(hl.wrapping_sub(1));
             }
-            Err(err) => panic!("{err:?}"),
+            Err(_) => return Err(self.decode_error()),
         }
+        Ok(())
     }
 
-    fn lda(&mut self, source: u8) {
+    fn lda(&mut self, source: u8) -> Result<(), CpuError> {
         match reg::R16Mem::try_from(source) {
             Ok(reg::R16Mem::BC) => {
-                self.registers.a = self.memory_bus.read_byte(self.registers.bc())
+                self.registers.a = self.read_byte(self.registers.bc())
             }
             Ok(reg::R16Mem::DE) => {
-                self.registers.a = self.memory_bus.read_byte(self.registers.de())
+                self.registers.a = self.read_byte(self.registers.de())
             }
             Ok(reg::R16Mem::HLI) => {
                 let hl = self.registers.hl();
-                self.registers.a = self.memory_bus.read_byte(hl);
+                self.registers.a = self.read_byte(hl);
                 self.registers.set_hl(hl.wrapping_add(1));
             }
             Ok(reg::R16Mem::HLD) => {
                 let hl = self.registers.hl();
-                self.registers.a = self.memory_bus.read_byte(hl);
+                self.registers.a = self.read_byte(hl);
                 self.registers.set_hl(hl.wrapping_sub(1));
             }
-            Err(err) => panic!("{err:?}"),
+            Err(_) => return Err(self.decode_error()),
         }
+        Ok(())
     }
 
     fn ldn16sp(&mut self) {
         let n16 = self.fetch_n16();
-        let sp_high: u8 = (self.registers.sp & 0x00FF) as u8;
-        let sp_low: u8 = (self.registers.sp >> 8) as u8;
-        self.memory_bus.set_byte(n16, sp_high);
-        self.memory_bus.set_byte(n16.wrapping_add(1), sp_low);
+        self.write_word(n16, self.registers.sp);
     }
 
-    fn incr16(&mut self, operand: u8) {
+    fn incr16(&mut self, operand: u8) -> Result<(), CpuError> {
+        // 16-bit increments go through the address adder, one extra M-cycle.
+        self.idle();
         match reg::R16::try_from(operand) {
             Ok(reg::R16::BC) => {
                 let current_bc = self.registers.bc();
@@ -208,11 +547,14 @@ impl CPU {
                 let current_sp = self.registers.sp;
                 self.registers.sp = current_sp.wrapping_add(1);
             }
-            Err(err) => panic!("{err:?}"),
+            Err(_) => return Err(self.decode_error()),
         }
+        Ok(())
     }
 
-    fn decr16(&mut self, operand: u8) {
+    fn decr16(&mut self, operand: u8) -> Result<(), CpuError> {
+        // 16-bit increments go through the address adder, one extra M-cycle.
+        self.idle();
         match reg::R16::try_from(operand) {
             Ok(reg::R16::BC) => {
                 let current_bc = self.registers.bc();
@@ -230,20 +572,22 @@ impl CPU {
                 let current_sp = self.registers.sp;
                 self.registers.sp = current_sp.wrapping_sub(1);
             }
-            Err(err) => panic!("{err:?}"),
+            Err(_) => return Err(self.decode_error()),
         }
+        Ok(())
     }
 
-    fn addhl(&mut self, operand: u8) {
+    fn addhl(&mut self, operand: u8) -> Result<(), CpuError> {
+        // The 8-bit ALU adds the low and high bytes on separate M-cycles.
+        self.idle();
         let old_hl = self.registers.hl();
-        let mut register_val: u16 = 0;
-        match reg::R16::try_from(operand) {
-            Ok(reg::R16::BC) => register_val = self.registers.bc(),
-            Ok(reg::R16::DE) => register_val = self.registers.de(),
-            Ok(reg::R16::HL) => register_val = self.registers.hl(),
-            Ok(reg::R16::SP) => register_val = self.registers.sp,
-            _ => {}
-        }
+        let register_val: u16 = match reg::R16::try_from(operand) {
+            Ok(reg::R16::BC) => self.registers.bc(),
+            Ok(reg::R16::DE) => self.registers.de(),
+            Ok(reg::R16::HL) => self.registers.hl(),
+            Ok(reg::R16::SP) => self.registers.sp,
+            Err(_) => return Err(self.decode_error()),
+        };
         let (res, carry) = old_hl.overflowing_add(register_val);
         self.registers.f.c = carry;
         self.registers.f.s = false;
@@ -252,105 +596,32 @@ impl CPU {
         self.registers.f.h = (register_val & mask) + (old_hl & mask) > mask;
 
         self.registers.set_hl(res);
+        Ok(())
     }
 
-    fn incr8(&mut self, operand: u8) {
-        let res: u8;
-        match reg::R8::try_from(operand) {
-            Ok(reg::R8::A) => {
-                res = self.registers.a.wrapping_add(1);
-                self.registers.a = res;
-            }
-            Ok(reg::R8::B) => {
-                res = self.registers.b.wrapping_add(1);
-                self.registers.b = res;
-            },
-            Ok(reg::R8::C) => {
-                res = self.registers.c.wrapping_add(1);
-                self.registers.c = res;
-            },
-            Ok(reg::R8::D) => {
-                res = self.registers.d.wrapping_add(1);
-                self.registers.c = res;
-            },
-            Ok(reg::R8::E) => {
-                res = self.registers.e.wrapping_add(1);
-                self.registers.e = res;
-            },
-            Ok(reg::R8::H) => {
-                res = self.registers.h.wrapping_add(1);
-                self.registers.h = res;
-            },
-            Ok(reg::R8::L) => {
-                res = self.registers.l.wrapping_add(1);
-                self.registers.h = res;
-            },
-            Ok(reg::R8::HL) => {
-                let hl_val = self.memory_bus.read_byte(self.registers.hl());
-                res = hl_val.wrapping_add(1);
-                self.memory_bus.set_byte(self.registers.hl(), res);
-            }
-            Err(err) => panic!("{err:?}"),
-        }
+    fn incr8(&mut self, operand: u8) -> Result<(), CpuError> {
+        let register_val = self.read_r8(operand)?;
+        let res = register_val.wrapping_add(1);
+        self.write_r8(operand, res)?;
 
         self.registers.f.z = res == 0;
         self.registers.f.s = false;
-        self.registers.f.h = (res & 0xF) == 0xF;
+        self.registers.f.h = (register_val & 0xF) == 0xF;
+        Ok(())
     }
 
-    fn decr8(&mut self, operand: u8) {
-        let res: u8;
-        let register_val: u8;
-        match reg::R8::try_from(operand) {
-            Ok(reg::R8::A) => {
-                register_val = self.registers.a;
-                res = register_val.wrapping_sub(1);
-                self.registers.a = res;
-            }
-            Ok(reg::R8::B) => {
-                register_val = self.registers.b;
-                res = register_val.wrapping_sub(1);
-                self.registers.b = res;
-            },
-            Ok(reg::R8::C) => {
-                register_val = self.registers.c;
-                res = register_val.wrapping_sub(1);
-                self.registers.c = res;
-            },
-            Ok(reg::R8::D) => {
-                register_val = self.registers.d;
-                res = register_val.wrapping_sub(1);
-                self.registers.c = res;
-            },
-            Ok(reg::R8::E) => {
-                register_val = self.registers.e;
-                res = register_val.wrapping_sub(1);
-                self.registers.e = res;
-            },
-            Ok(reg::R8::H) => {
-                register_val = self.registers.h;
-                res = register_val.wrapping_sub(1);
-                self.registers.h = res;
-            },
-            Ok(reg::R8::L) => {
-                register_val = self.registers.l;
-                res = register_val.wrapping_sub(1);
-                self.registers.h = res;
-            },
-            Ok(reg::R8::HL) => {
-                register_val = self.memory_bus.read_byte(self.registers.hl());
-                res = register_val.wrapping_sub(1);
-                self.memory_bus.set_byte(self.registers.hl(), res);
-            }
-            Err(err) => panic!("{err:?}"),
-        }
+    fn decr8(&mut self, operand: u8) -> Result<(), CpuError> {
+        let register_val = self.read_r8(operand)?;
+        let res = register_val.wrapping_sub(1);
+        self.write_r8(operand, res)?;
 
         self.registers.f.z = res == 0;
         self.registers.f.s = true;
-        self.registers.f.h = (register_val & 0x1) == 0x10;
+        self.registers.f.h = (register_val & 0xF) == 0x0;
+        Ok(())
     }
 
-    fn ldr8n8(&mut self, dest: u8) {
+    fn ldr8n8(&mut self, dest: u8) -> Result<(), CpuError> {
         let n8: u8 = self.fetch();
         match reg::R8::try_from(dest) {
             Ok(reg::R8::B) => self.registers.b = n8,
@@ -360,11 +631,12 @@ impl CPU {
             Ok(reg::R8::H) => self.registers.h = n8,
             Ok(reg::R8::L) => self.registers.l = n8,
             Ok(reg::R8::HL) => {
-                self.memory_bus.set_byte(self.registers.hl(), n8);
+                self.write_byte(self.registers.hl(), n8);
             }
             Ok(reg::R8::A) => self.registers.a = n8,
-            Err(err) => panic!("{err:?}"),
+            Err(_) => return Err(self.decode_error()),
         }
+        Ok(())
     }
 
     fn rlca(&mut self) {
@@ -417,9 +689,9 @@ impl CPU {
             if self.registers.f.h {
                 adjustment |= 0x6;
             }
+            // Borrows are only ever undone here, so C is left as it was.
             if self.registers.f.c {
                 adjustment |= 0x60;
-                self.registers.f.c = false;
             }
             self.registers.a = self.registers.a.wrapping_sub(adjustment);
         } else {
@@ -434,8 +706,7 @@ impl CPU {
             self.registers.a = self.registers.a.wrapping_add(adjustment);
         }
 
-        // If the adjustment was
-        self.registers.f.z = adjustment == 0;
+        self.registers.f.z = self.registers.a == 0;
         self.registers.f.h = false;
     }
 
@@ -458,44 +729,384 @@ impl CPU {
     }
 
     fn jrn8(&mut self) {
-        self.jump_relative();
+        let n8: i8 = self.fetch() as i8;
+        self.jump_relative(n8);
     }
 
-    fn jrcondn8(&mut self, cond: u8) {
-        match cond {
-            0x0 => {
-                if !self.registers.f.z {
-                    self.jump_relative();
-                }
-            }
-            0x1 => {
-                if self.registers.f.z {
-                    self.jump_relative();
-                }
-            }
-            0x2 => {
-                if !self.registers.f.c {
-                    self.jump_relative();
-                }
-            }
-            0x3 => {
-                if self.registers.f.c {
-                    self.jump_relative();
-                }
-            }
-            _ => {
-                panic!("cond: {cond} not valid, could not jump")
-            }
+    fn jrcondn8(&mut self, cond: u8) -> Result<(), CpuError> {
+        // The offset is part of the instruction, so it is consumed even when
+        // the jump is not taken.
+        let n8: i8 = self.fetch() as i8;
+        if self.condition(cond)? {
+            self.jump_relative(n8);
         }
+        Ok(())
     }
 
+    /* STOP is two bytes long, but the byte after the opcode is skipped over
+     * without being read.
+     */
     fn stop(&mut self) {
-        // TODO: Implement STOP. This will require
+        self.registers.advance_pc();
+        // TODO: Implement STOP. This will require a low-power mode that waits
+        // for a joypad press, resetting DIV, and CGB speed switching via KEY1.
     }
 
-    fn jump_relative(&mut self) {
-        let n8: i8 = self.fetch() as i8;
-        self.registers.sp = self.registers.sp.wrapping_add_signed(n8.into());
+    /* A taken relative jump spends an extra M-cycle adding the offset. */
+    fn jump_relative(&mut self, n8: i8) {
+        self.registers.pc = self.registers.pc.wrapping_add_signed(n8.into());
+        self.idle();
     }
     // Begin Block 1 Helper Functions
+    fn ldr8r8(&mut self, dest: u8, source: u8) -> Result<(), CpuError> {
+        let val = self.read_r8(source)?;
+        self.write_r8(dest, val)?;
+        Ok(())
+    }
+
+    /* HALT suspends fetching until an enabled interrupt is pending. If one is
+     * already pending while IME is off, the CPU does not halt at all and
+     * instead trips the HALT bug: the next opcode byte is read twice.
+     */
+    fn halt(&mut self) {
+        if self.pending_interrupts() == 0 {
+            self.halted = true;
+        } else if !self.ime {
+            self.halt_bug = true;
+        }
+    }
+
+    // Begin Block 2 Helper Functions
+
+    /* The 8-bit ALU. Block 2 feeds these from an R8 operand and Block 3 from
+     * the immediate byte, so every A-register arithmetic flag is set here.
+     */
+    fn alu_add(&mut self, val: u8, with_carry: bool) {
+        let a = self.registers.a;
+        let carry: u8 = if with_carry && self.registers.f.c { 1 } else { 0 };
+        let res = a.wrapping_add(val).wrapping_add(carry);
+
+        self.registers.f.z = res == 0;
+        self.registers.f.s = false;
+        self.registers.f.h = (a & 0xF) + (val & 0xF) + carry > 0xF;
+        self.registers.f.c = (a as u16) + (val as u16) + (carry as u16) > 0xFF;
+        self.registers.a = res;
+    }
+
+    fn alu_sub(&mut self, val: u8, with_carry: bool) {
+        self.registers.a = self.subtract(val, with_carry);
+    }
+
+    fn alu_cp(&mut self, val: u8) {
+        self.subtract(val, false);
+    }
+
+    /* Shared by SUB, SBC and CP: sets the flags for A - val and returns the
+     * result without storing it.
+     */
+    fn subtract(&mut self, val: u8, with_carry: bool) -> u8 {
+        let a = self.registers.a;
+        let carry: u8 = if with_carry && self.registers.f.c { 1 } else { 0 };
+        let res = a.wrapping_sub(val).wrapping_sub(carry);
+
+        self.registers.f.z = res == 0;
+        self.registers.f.s = true;
+        self.registers.f.h = (a & 0xF) < (val & 0xF) + carry;
+        self.registers.f.c = (a as u16) < (val as u16) + (carry as u16);
+        res
+    }
+
+    fn alu_and(&mut self, val: u8) {
+        self.registers.a &= val;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.s = false;
+        self.registers.f.h = true;
+        self.registers.f.c = false;
+    }
+
+    fn alu_xor(&mut self, val: u8) {
+        self.registers.a ^= val;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.s = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+    }
+
+    fn alu_or(&mut self, val: u8) {
+        self.registers.a |= val;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.s = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+    }
+
+    // Begin Block 3 Helper Functions
+    /* Unlike JP and CALL, RET cc spends an M-cycle checking the condition,
+     * whether or not it is taken.
+     */
+    fn retcond(&mut self, cond: u8) -> Result<(), CpuError> {
+        self.idle();
+        if self.condition(cond)? {
+            self.ret();
+        }
+        Ok(())
+    }
+
+    fn ret(&mut self) {
+        self.registers.pc = self.pop_word();
+        self.idle();
+    }
+
+    fn reti(&mut self) {
+        self.ret();
+        self.ime = true;
+    }
+
+    fn jpcondn16(&mut self, cond: u8) -> Result<(), CpuError> {
+        let n16 = self.fetch_n16();
+        if self.condition(cond)? {
+            self.registers.pc = n16;
+            self.idle();
+        }
+        Ok(())
+    }
+
+    fn jpn16(&mut self) {
+        self.registers.pc = self.fetch_n16();
+        self.idle();
+    }
+
+    fn callcondn16(&mut self, cond: u8) -> Result<(), CpuError> {
+        let n16 = self.fetch_n16();
+        if self.condition(cond)? {
+            self.call(n16);
+        }
+        Ok(())
+    }
+
+    fn calln16(&mut self) {
+        let n16 = self.fetch_n16();
+        self.call(n16);
+    }
+
+    fn call(&mut self, address: u16) {
+        self.push_word(self.registers.pc);
+        self.registers.pc = address;
+    }
+
+    /* RST jumps to one of eight fixed vectors: 0x00, 0x08, ..., 0x38. */
+    fn rst(&mut self, tgt3: u8) {
+        self.call((tgt3 as u16) * 8);
+    }
+
+    fn pop(&mut self, register: u8) -> Result<(), CpuError> {
+        let val = self.pop_word();
+        match reg::R16Stk::try_from(register) {
+            Ok(reg::R16Stk::BC) => self.registers.set_bc(val),
+            Ok(reg::R16Stk::DE) => self.registers.set_de(val),
+            Ok(reg::R16Stk::HL) => self.registers.set_hl(val),
+            Ok(reg::R16Stk::AF) => self.registers.set_af(val),
+            Err(_) => return Err(self.decode_error()),
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, register: u8) -> Result<(), CpuError> {
+        let val = match reg::R16Stk::try_from(register) {
+            Ok(reg::R16Stk::BC) => self.registers.bc(),
+            Ok(reg::R16Stk::DE) => self.registers.de(),
+            Ok(reg::R16Stk::HL) => self.registers.hl(),
+            Ok(reg::R16Stk::AF) => self.registers.af(),
+            Err(_) => return Err(self.decode_error()),
+        };
+        self.push_word(val);
+        Ok(())
+    }
+
+    fn addspn8(&mut self) {
+        self.registers.sp = self.sp_plus_n8();
+        self.idle();
+        self.idle();
+    }
+
+    fn ldhlspn8(&mut self) {
+        let res = self.sp_plus_n8();
+        self.registers.set_hl(res);
+        self.idle();
+    }
+
+    /* Shared by ADD SP, n8 and LD HL, SP + n8. The offset is signed, but H and
+     * C come from unsigned addition on the low byte of SP.
+     */
+    fn sp_plus_n8(&mut self) -> u16 {
+        let n8 = self.fetch();
+        let sp = self.registers.sp;
+
+        self.registers.f.z = false;
+        self.registers.f.s = false;
+        self.registers.f.h = (sp & 0xF) + (n8 as u16 & 0xF) > 0xF;
+        self.registers.f.c = (sp & 0xFF) + (n8 as u16) > 0xFF;
+        sp.wrapping_add_signed((n8 as i8).into())
+    }
+
+    fn di(&mut self) {
+        self.ime = false;
+        self.ime_delay = 0;
+    }
+
+    /* EI only sets IME once the instruction after it has finished, which is
+     * why `EI; RET` returns before any interrupt gets serviced. A second EI
+     * inside that window does not push it back any further.
+     */
+    fn ei(&mut self) {
+        if self.ime_delay == 0 {
+            self.ime_delay = 2;
+        }
+    }
+
+    // Begin Prefixed Helper Functions
+
+    /* Every CB rotate/shift is a read-modify-write of an R8 operand with the
+     * same flag rules, so they only differ in `op`, which is handed the value
+     * and the current carry and returns the result and the new carry bit.
+     */
+    fn shift(&mut self, operand: u8, op: impl Fn(u8, u8) -> (u8, u8)) -> Result<(), CpuError> {
+        let val = self.read_r8(operand)?;
+        let c: u8 = if self.registers.f.c { 1 } else { 0 };
+        let (res, carry) = op(val, c);
+
+        self.registers.f.z = res == 0;
+        self.registers.f.s = false;
+        self.registers.f.h = false;
+        self.registers.f.c = carry != 0;
+        self.write_r8(operand, res)?;
+        Ok(())
+    }
+
+    fn bit(&mut self, b3: u8, operand: u8) -> Result<(), CpuError> {
+        let val = self.read_r8(operand)?;
+        self.registers.f.z = (val >> b3) & 1 == 0;
+        self.registers.f.s = false;
+        self.registers.f.h = true;
+        Ok(())
+    }
+
+    // Begin Shared Helper Functions
+
+    /* Every memory access the CPU makes costs one M-cycle, and the rest of
+     * the system is ticked right after it.
+     */
+    fn read_byte(&mut self, address: u16) -> u8 {
+        let val = self.memory_bus.read(address);
+        self.tick();
+        val
+    }
+
+    fn write_byte(&mut self, address: u16, val: u8) {
+        self.memory_bus.write(address, val);
+        self.tick();
+    }
+
+    /* Little-endian word access on top of read_byte and write_byte, so the
+     * low byte goes first and each byte costs an M-cycle. The address wraps
+     * from 0xFFFF to 0x0000.
+     */
+    fn read_word(&mut self, address: u16) -> u16 {
+        let low = self.read_byte(address);
+        let high = self.read_byte(address.wrapping_add(1));
+        (high as u16) << 8 | low as u16
+    }
+
+    fn write_word(&mut self, address: u16, val: u16) {
+        self.write_byte(address, (val & 0x00FF) as u8);
+        self.write_byte(address.wrapping_add(1), (val >> 8) as u8);
+    }
+
+    /* An M-cycle spent on internal work without touching the bus. */
+    fn idle(&mut self) {
+        self.tick();
+    }
+
+    fn tick(&mut self) {
+        self.step_cycles += 1;
+        self.memory_bus.tick();
+    }
+
+    fn condition(&self, cond: u8) -> Result<bool, CpuError> {
+        match reg::Cond::try_from(cond) {
+            Ok(reg::Cond::NZ) => Ok(!self.registers.f.z),
+            Ok(reg::Cond::Z) => Ok(self.registers.f.z),
+            Ok(reg::Cond::NC) => Ok(!self.registers.f.c),
+            Ok(reg::Cond::C) => Ok(self.registers.f.c),
+            Err(_) => Err(self.decode_error()),
+        }
+    }
+
+    /* Reports the instruction being executed as something the decoder got
+     * wrong, e.g. an operand outside of its register enum.
+     */
+    fn decode_error(&self) -> CpuError {
+        let (address, opcode, prefixed) = self.current;
+        CpuError::Decode {
+            address,
+            opcode,
+            prefixed,
+        }
+    }
+
+    /* The stack grows downwards. The word ends up little-endian in memory
+     * like any other, but the hardware writes the high byte first, so this
+     * can't go through write_word. Pushing always starts with an internal
+     * M-cycle spent decrementing SP.
+     */
+    fn push_word(&mut self, val: u16) {
+        self.idle();
+        self.registers.sp = self.registers.sp.wrapping_sub(1);
+        self.write_byte(self.registers.sp, (val >> 8) as u8);
+        self.registers.sp = self.registers.sp.wrapping_sub(1);
+        self.write_byte(self.registers.sp, (val & 0x00FF) as u8);
+    }
+
+    fn pop_word(&mut self) -> u16 {
+        let val = self.read_word(self.registers.sp);
+        self.registers.sp = self.registers.sp.wrapping_add(2);
+        val
+    }
+
+    /* Reads the value an R8 operand refers to. Operand 6 is not a register
+     * but the byte in memory that HL points at.
+     */
+    fn read_r8(&mut self, operand: u8) -> Result<u8, CpuError> {
+        match reg::R8::try_from(operand) {
+            Ok(reg::R8::B) => Ok(self.registers.b),
+            Ok(reg::R8::C) => Ok(self.registers.c),
+            Ok(reg::R8::D) => Ok(self.registers.d),
+            Ok(reg::R8::E) => Ok(self.registers.e),
+            Ok(reg::R8::H) => Ok(self.registers.h),
+            Ok(reg::R8::L) => Ok(self.registers.l),
+            Ok(reg::R8::HL) => Ok(self.read_byte(self.registers.hl())),
+            Ok(reg::R8::A) => Ok(self.registers.a),
+            Err(_) => Err(self.decode_error()),
+        }
+    }
+
+    fn write_r8(&mut self, operand: u8, val: u8) -> Result<(), CpuError> {
+        match reg::R8::try_from(operand) {
+            Ok(reg::R8::B) => self.registers.b = val,
+            Ok(reg::R8::C) => self.registers.c = val,
+            Ok(reg::R8::D) => self.registers.d = val,
+            Ok(reg::R8::E) => self.registers.e = val,
+            Ok(reg::R8::H) => self.registers.h = val,
+            Ok(reg::R8::L) => self.registers.l = val,
+            Ok(reg::R8::HL) => self.write_byte(self.registers.hl(), val),
+            Ok(reg::R8::A) => self.registers.a = val,
+            Err(_) => return Err(self.decode_error()),
+        }
+        Ok(())
+    }
+
+    /* Interrupts that are both requested (IF) and enabled (IE). */
+    fn pending_interrupts(&mut self) -> u8 {
+        self.memory_bus.pending_interrupts()
+    }
 }
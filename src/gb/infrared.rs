@@ -0,0 +1,106 @@
+use std::cell::Cell;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/* One end of an infrared link: an LED we control and a receiver that sees
+ * the LED on the other end. Used by the CGB's RP register and by the HuC1
+ * and HuC3 cartridges, which all have the same kind of IR port.
+ */
+pub trait IrChannel: Debug {
+    fn set_led(&mut self, on: bool);
+
+    /* Whether the receiver currently sees light. */
+    fn receiving(&self) -> bool;
+}
+
+/* Nothing on the other end, so no light ever comes in. */
+#[derive(Debug, Default)]
+pub struct Disconnected;
+
+impl IrChannel for Disconnected {
+    fn set_led(&mut self, _on: bool) {}
+
+    fn receiving(&self) -> bool {
+        false
+    }
+}
+
+/* A port linked to exactly one other, e.g. two emulated Game Boys pointed
+ * at each other. Each end's LED is the other end's receiver.
+ */
+#[derive(Debug)]
+pub struct IrPort {
+    led: Rc<Cell<bool>>,
+    peer_led: Rc<Cell<bool>>,
+}
+
+impl IrPort {
+    pub fn pair() -> (IrPort, IrPort) {
+        let a = Rc::new(Cell::new(false));
+        let b = Rc::new(Cell::new(false));
+        (
+            IrPort {
+                led: a.clone(),
+                peer_led: b.clone(),
+            },
+            IrPort {
+                led: b,
+                peer_led: a,
+            },
+        )
+    }
+}
+
+impl IrChannel for IrPort {
+    fn set_led(&mut self, on: bool) {
+        self.led.set(on);
+    }
+
+    fn receiving(&self) -> bool {
+        self.peer_led.get()
+    }
+}
+
+/* The CGB's IR port, controlled through RP at 0xFF56:
+ *
+ * bit 0    LED, 1 is on
+ * bit 1    Receiver, 0 while light is coming in. Reads 1 unless reading
+ *          is enabled.
+ * bit 6-7  Both set to enable reading
+ */
+#[derive(Debug)]
+pub struct Infrared {
+    channel: Box<dyn IrChannel>,
+    rp: u8,
+}
+
+impl Default for Infrared {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Infrared {
+    pub fn new() -> Infrared {
+        Infrared {
+            channel: Box::new(Disconnected),
+            rp: 0x00,
+        }
+    }
+
+    pub fn connect(&mut self, channel: Box<dyn IrChannel>) {
+        self.channel = channel;
+        self.channel.set_led(self.rp & 0x01 != 0);
+    }
+
+    pub fn read_rp(&self) -> u8 {
+        let reading = self.rp & 0xC0 == 0xC0;
+        let dark = !(reading && self.channel.receiving());
+        0x3C | self.rp | (dark as u8) << 1
+    }
+
+    pub fn write_rp(&mut self, val: u8) {
+        self.rp = val & 0xC1;
+        self.channel.set_led(val & 0x01 != 0);
+    }
+}
@@ -5,7 +5,9 @@
  * CPU will have to grab from memory)
  */
 
-#[derive(Debug)]
+use crate::gb::opcodes::Opcode;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Instruction {
     Block0(B0Instruction),
     Block1(B1Instruction),
@@ -22,7 +24,7 @@ pub enum Instruction {
  * n8: The next 8 bits.
  * n16: The next 16 bits.
  */
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum B0Instruction {
     NOP,
     LDR16N16(u8),
@@ -48,13 +50,13 @@ pub enum B0Instruction {
     STOP,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum B1Instruction {
     LD { dest: u8, source: u8 },
     HALT,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum B2Instruction {
     ADD(u8),
     ADC(u8),
@@ -66,7 +68,7 @@ pub enum B2Instruction {
     CP(u8),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum B3Instruction {
     ADDN8,
     ADCN8,
@@ -87,9 +89,12 @@ pub enum B3Instruction {
     RST(u8),
     POP(u8),
     PUSH(u8),
-    LDHC,
-    LDHN8,
-    LDN16,
+    LDHCA,
+    LDHAC,
+    LDHN8A,
+    LDHAN8,
+    LDN16A,
+    LDAN16,
     ADDSPN8,
     LDHLSPN8,
     LDSPHL,
@@ -97,7 +102,7 @@ pub enum B3Instruction {
     EI,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PrefixedInstruction {
     RLC(u8),
     RRC(u8),
@@ -112,20 +117,29 @@ pub enum PrefixedInstruction {
     SET { b3: u8, operand: u8 },
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InstructionError {
     NotFound,
+    // One of the eleven opcodes the SM83 leaves unused.
     Invalid,
 }
 
 impl Instruction {
     /* from_byte is the publicly exposed method that allows for a single byte
-     * to be turned into an Instruction enum. It splits paths depending on
-     * whether or not an instruction is marked as prefixed (0xCB) or not.
+     * to be turned into an Instruction enum. The work is done ahead of time:
+     * every opcode is decoded into the static tables in opcodes.rs at compile
+     * time, so this is just a lookup.
      */
     pub fn from_byte(byte: u8, prefixed: bool) -> Result<Instruction, InstructionError> {
+        Opcode::lookup(byte, prefixed).instruction
+    }
+
+    /* decode is what the opcode tables are built from. It splits paths
+     * depending on whether or not an instruction is marked as prefixed (0xCB)
+     * or not.
+     */
+    pub const fn decode(byte: u8, prefixed: bool) -> Result<Instruction, InstructionError> {
         if prefixed {
-            println!("prefixed");
             Instruction::from_byte_prefixed(byte)
         } else {
             Instruction::from_byte_not_cb(byte)
@@ -138,9 +152,9 @@ impl Instruction {
      * use the lowest bits of the byte as the operand.
      *
      */
-    fn from_byte_prefixed(byte: u8) -> Result<Instruction, InstructionError> {
+    const fn from_byte_prefixed(byte: u8) -> Result<Instruction, InstructionError> {
         let block: u8 = byte >> 6;
-        let operand: u8 = (byte & 0x7) as u8;
+        let operand: u8 = byte & 0x7;
         let b3: u8 = (byte >> 3) & 0x7;
         match block {
             0b00 => Instruction::from_cb_zero_block(byte, operand),
@@ -160,7 +174,7 @@ impl Instruction {
         }
     }
 
-    fn from_cb_zero_block(byte: u8, operand: u8) -> Result<Instruction, InstructionError> {
+    const fn from_cb_zero_block(byte: u8, operand: u8) -> Result<Instruction, InstructionError> {
         let u5: u8 = byte >> 3;
 
         match u5 {
@@ -176,7 +190,7 @@ impl Instruction {
         }
     }
 
-    fn from_byte_not_cb(byte: u8) -> Result<Instruction, InstructionError> {
+    const fn from_byte_not_cb(byte: u8) -> Result<Instruction, InstructionError> {
         let block = byte >> 6;
         match block {
             0b00 => Instruction::from_byte_zero_block(byte),
@@ -187,7 +201,7 @@ impl Instruction {
         }
     }
 
-    fn from_byte_zero_block(byte: u8) -> Result<Instruction, InstructionError> {
+    const fn from_byte_zero_block(byte: u8) -> Result<Instruction, InstructionError> {
         match byte {
             0x00 => Ok(Instruction::Block0(B0Instruction::NOP)),
             0x07 => Ok(Instruction::Block0(B0Instruction::RLCA)),
@@ -205,13 +219,12 @@ impl Instruction {
         }
     }
 
-    fn from_byte_zero_block_u4(byte: u8) -> Result<Instruction, InstructionError> {
+    const fn from_byte_zero_block_u4(byte: u8) -> Result<Instruction, InstructionError> {
         let r16: u8 = (byte >> 4) & 0x3;
         match byte & 0x0F {
             0x1 => Ok(Instruction::Block0(B0Instruction::LDR16N16(r16))),
             0x2 => Ok(Instruction::Block0(B0Instruction::LDR16(r16))),
             0x3 => Ok(Instruction::Block0(B0Instruction::INCR16(r16))),
-            0x8 => Ok(Instruction::Block0(B0Instruction::LDN16SP)),
             0x9 => Ok(Instruction::Block0(B0Instruction::ADDHL(r16))),
             0xA => Ok(Instruction::Block0(B0Instruction::LDA(r16))),
             0xB => Ok(Instruction::Block0(B0Instruction::DECR16(r16))),
@@ -219,7 +232,7 @@ impl Instruction {
         }
     }
 
-    fn from_byte_zero_block_u3(byte: u8) -> Result<Instruction, InstructionError> {
+    const fn from_byte_zero_block_u3(byte: u8) -> Result<Instruction, InstructionError> {
         let r8: u8 = (byte >> 3) & 0x7;
         match byte & 0x7 {
             0x0 => {
@@ -233,7 +246,7 @@ impl Instruction {
         }
     }
 
-    fn from_byte_one_block(byte: u8) -> Result<Instruction, InstructionError> {
+    const fn from_byte_one_block(byte: u8) -> Result<Instruction, InstructionError> {
         let dest: u8 = (byte >> 3) & 0x7;
         let source: u8 = byte & 0x7;
         match byte {
@@ -242,7 +255,7 @@ impl Instruction {
         }
     }
 
-    fn from_byte_two_block(byte: u8) -> Result<Instruction, InstructionError> {
+    const fn from_byte_two_block(byte: u8) -> Result<Instruction, InstructionError> {
         let first_five: u8 = byte >> 3;
         let operand: u8 = byte & 0x7;
         match first_five {
@@ -257,7 +270,7 @@ impl Instruction {
             _ => Err(InstructionError::NotFound),
         }
     }
-    fn from_byte_three_block(byte: u8) -> Result<Instruction, InstructionError> {
+    const fn from_byte_three_block(byte: u8) -> Result<Instruction, InstructionError> {
         match byte {
             0xC3 => Ok(Instruction::Block3(B3Instruction::JPN16)),
             0xC6 => Ok(Instruction::Block3(B3Instruction::ADDN8)),
@@ -267,27 +280,32 @@ impl Instruction {
             0xD6 => Ok(Instruction::Block3(B3Instruction::SUBN8)),
             0xD9 => Ok(Instruction::Block3(B3Instruction::RETI)),
             0xDE => Ok(Instruction::Block3(B3Instruction::SBCN8)),
-            0xE0 => Ok(Instruction::Block3(B3Instruction::LDHN8)),
-            0xE2 => Ok(Instruction::Block3(B3Instruction::LDHC)),
+            0xE0 => Ok(Instruction::Block3(B3Instruction::LDHN8A)),
+            0xE2 => Ok(Instruction::Block3(B3Instruction::LDHCA)),
             0xE6 => Ok(Instruction::Block3(B3Instruction::ANDN8)),
             0xE8 => Ok(Instruction::Block3(B3Instruction::ADDSPN8)),
             0xE9 => Ok(Instruction::Block3(B3Instruction::JPHL)),
-            0xEA => Ok(Instruction::Block3(B3Instruction::LDN16)),
+            0xEA => Ok(Instruction::Block3(B3Instruction::LDN16A)),
             0xEE => Ok(Instruction::Block3(B3Instruction::XORN8)),
-            0xF0 => Ok(Instruction::Block3(B3Instruction::LDHN8)),
-            0xF2 => Ok(Instruction::Block3(B3Instruction::LDHC)),
+            0xF0 => Ok(Instruction::Block3(B3Instruction::LDHAN8)),
+            0xF2 => Ok(Instruction::Block3(B3Instruction::LDHAC)),
             0xF3 => Ok(Instruction::Block3(B3Instruction::DI)),
             0xF6 => Ok(Instruction::Block3(B3Instruction::ORN8)),
             0xF8 => Ok(Instruction::Block3(B3Instruction::LDHLSPN8)),
             0xF9 => Ok(Instruction::Block3(B3Instruction::LDSPHL)),
-            0xFA => Ok(Instruction::Block3(B3Instruction::LDN16)),
+            0xFA => Ok(Instruction::Block3(B3Instruction::LDAN16)),
             0xFB => Ok(Instruction::Block3(B3Instruction::EI)),
             0xFE => Ok(Instruction::Block3(B3Instruction::CPN8)),
+            // These would otherwise fall through to the RET/JP/CALL cond and
+            // PUSH patterns below, which they only look like.
+            0xD3 | 0xDB | 0xDD | 0xE3 | 0xE4 | 0xEB | 0xEC | 0xED | 0xF4 | 0xFC | 0xFD => {
+                Err(InstructionError::Invalid)
+            }
             _ => Instruction::from_byte_three_block_u3(byte),
         }
     }
 
-    fn from_byte_three_block_u3(byte: u8) -> Result<Instruction, InstructionError> {
+    const fn from_byte_three_block_u3(byte: u8) -> Result<Instruction, InstructionError> {
         let cond: u8 = (byte >> 3) & 0x3;
         let register: u8 = (byte >> 4) & 0x3;
         let tgt3: u8 = (byte >> 3) & 0x7;
@@ -0,0 +1,89 @@
+/* Interrupt sources in priority order. The discriminant is the bit each one
+ * occupies in the IE (0xFFFF) and IF (0xFF0F) registers.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterruptKind {
+    VBlank = 0,
+    LcdStat = 1,
+    Timer = 2,
+    Serial = 3,
+    Joypad = 4,
+}
+
+impl InterruptKind {
+    pub const ALL: [InterruptKind; 5] = [
+        InterruptKind::VBlank,
+        InterruptKind::LcdStat,
+        InterruptKind::Timer,
+        InterruptKind::Serial,
+        InterruptKind::Joypad,
+    ];
+
+    pub fn mask(self) -> u8 {
+        1 << self as u8
+    }
+
+    /* Handlers live at 0x40, 0x48, 0x50, 0x58 and 0x60. */
+    pub fn vector(self) -> u16 {
+        0x40 + 8 * self as u16
+    }
+
+    /* Picks the highest priority interrupt set in `bits`, if any. */
+    pub fn highest(bits: u8) -> Option<InterruptKind> {
+        InterruptKind::ALL
+            .into_iter()
+            .find(|kind| bits & kind.mask() != 0)
+    }
+}
+
+#[derive(Debug)]
+pub struct Interrupts {
+    enable: u8,
+    flag: u8,
+}
+
+impl Default for Interrupts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Interrupts {
+    pub fn new() -> Interrupts {
+        Interrupts {
+            enable: 0x00,
+            flag: 0x00,
+        }
+    }
+
+    pub fn request(&mut self, kind: InterruptKind) {
+        self.flag |= kind.mask();
+    }
+
+    pub fn acknowledge(&mut self, kind: InterruptKind) {
+        self.flag &= !kind.mask();
+    }
+
+    /* Interrupts that are both requested and enabled. */
+    pub fn pending(&self) -> u8 {
+        self.flag & self.enable & 0x1F
+    }
+
+    /* Only the low five bits of IF exist; the rest always read back as 1. */
+    pub fn read_flag(&self) -> u8 {
+        self.flag | 0xE0
+    }
+
+    pub fn write_flag(&mut self, val: u8) {
+        self.flag = val & 0x1F;
+    }
+
+    /* IE is a full byte of plain storage, even the three unused bits. */
+    pub fn read_enable(&self) -> u8 {
+        self.enable
+    }
+
+    pub fn write_enable(&mut self, val: u8) {
+        self.enable = val;
+    }
+}
@@ -0,0 +1,354 @@
+use crate::gb::mbc::{load_ram, ram_bank_offset, read_rom_bank, MemoryBankController};
+use std::path::Path;
+use std::{fmt, fs, io};
+
+const RAM_SIZE: usize = 0x20000;
+
+// The part of the sensor the cartridge reads out, in pixels.
+pub const IMAGE_WIDTH: usize = 128;
+pub const IMAGE_HEIGHT: usize = 112;
+
+// Captures land in the first RAM bank as 16 x 14 tiles, after 0x100 bytes
+// the camera ROM uses for itself.
+const IMAGE_OFFSET: usize = 0x100;
+
+const REGISTERS: usize = 0x36;
+const DITHER_MATRIX: usize = 0x06;
+
+// Edge enhancement strength for each setting of A004 bits 4-6.
+const EDGE_RATIOS: [f32; 8] = [0.5, 0.75, 1.0, 1.25, 2.0, 3.0, 4.0, 5.0];
+
+#[derive(Debug)]
+pub enum CameraImageError {
+    Io(io::Error),
+    // Not a PGM file we can read.
+    Format,
+    // Only images the size of the sensor are accepted.
+    Dimensions { width: usize, height: usize },
+    // The pixel buffer doesn't hold width * height pixels.
+    Size { expected: usize, actual: usize },
+}
+
+impl fmt::Display for CameraImageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CameraImageError::Io(err) => write!(f, "could not read image: {err}"),
+            CameraImageError::Format => write!(f, "image is not a PGM file"),
+            CameraImageError::Dimensions { width, height } => {
+                write!(f, "image is {width}x{height}, expected {IMAGE_WIDTH}x{IMAGE_HEIGHT}")
+            }
+            CameraImageError::Size { expected, actual } => {
+                write!(f, "image has {actual} pixels, expected {expected}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CameraImageError {}
+
+impl From<io::Error> for CameraImageError {
+    fn from(err: io::Error) -> Self {
+        CameraImageError::Io(err)
+    }
+}
+
+/* What the camera sensor is pointed at: a 128 x 112 grayscale still, 0 for
+ * black and 255 for white.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CameraImage {
+    pixels: Vec<u8>,
+}
+
+impl CameraImage {
+    /* A grayscale buffer of `width` x `height` pixels, row by row. The
+     * size has to match the sensor's.
+     */
+    pub fn from_grayscale(
+        width: usize,
+        height: usize,
+        pixels: &[u8],
+    ) -> Result<CameraImage, CameraImageError> {
+        let expected = check_dimensions(width, height)?;
+        if pixels.len() != expected {
+            return Err(CameraImageError::Size {
+                expected,
+                actual: pixels.len(),
+            });
+        }
+        Ok(CameraImage {
+            pixels: pixels.to_vec(),
+        })
+    }
+
+    /* Parses a binary (P5) or plain (P2) PGM image. PGM keeps us free of
+     * image decoding dependencies, and any image tool can convert to it.
+     */
+    pub fn from_pgm(data: &[u8]) -> Result<CameraImage, CameraImageError> {
+        let mut pos = 0;
+        let magic = pgm_token(data, &mut pos).ok_or(CameraImageError::Format)?;
+        let mut header = [0usize; 3];
+        for field in &mut header {
+            *field = pgm_number(data, &mut pos)?;
+        }
+        let [width, height, max] = header;
+        if max == 0 || max > 0xFFFF {
+            return Err(CameraImageError::Format);
+        }
+        // Checked before reading any pixels, so a bogus header can't make
+        // us allocate for them.
+        let size = check_dimensions(width, height)?;
+        let scale = |val: usize| (val.min(max) * 255 / max) as u8;
+
+        let pixels: Vec<u8> = match magic {
+            b"P5" => {
+                // A single whitespace byte separates the header from the data.
+                let data = data.get(pos + 1..).ok_or(CameraImageError::Format)?;
+                if max < 0x100 {
+                    data.iter().take(size).map(|val| scale(*val as usize)).collect()
+                } else {
+                    data.chunks_exact(2)
+                        .take(size)
+                        .map(|val| scale((val[0] as usize) << 8 | val[1] as usize))
+                        .collect()
+                }
+            }
+            b"P2" => (0..size)
+                .map(|_| pgm_number(data, &mut pos).map(scale))
+                .collect::<Result<_, _>>()?,
+            _ => return Err(CameraImageError::Format),
+        };
+        CameraImage::from_grayscale(width, height, &pixels)
+    }
+
+    pub fn open(path: impl AsRef<Path>) -> Result<CameraImage, CameraImageError> {
+        CameraImage::from_pgm(&fs::read(path)?)
+    }
+
+    fn pixel(&self, x: usize, y: usize) -> u8 {
+        self.pixels[y * IMAGE_WIDTH + x]
+    }
+}
+
+impl Default for CameraImage {
+    /* A flat mid gray, for when nothing has been supplied. */
+    fn default() -> Self {
+        CameraImage {
+            pixels: vec![0x80; IMAGE_WIDTH * IMAGE_HEIGHT],
+        }
+    }
+}
+
+/* Returns the number of pixels in a `width` x `height` image if that is the
+ * size of the sensor.
+ */
+fn check_dimensions(width: usize, height: usize) -> Result<usize, CameraImageError> {
+    let size = width.checked_mul(height).ok_or(CameraImageError::Format)?;
+    if (width, height) != (IMAGE_WIDTH, IMAGE_HEIGHT) {
+        return Err(CameraImageError::Dimensions { width, height });
+    }
+    Ok(size)
+}
+
+/* Next whitespace separated token of a PGM header, skipping comments. */
+fn pgm_token<'a>(data: &'a [u8], pos: &mut usize) -> Option<&'a [u8]> {
+    loop {
+        match data.get(*pos)? {
+            byte if byte.is_ascii_whitespace() => *pos += 1,
+            b'#' => {
+                while data.get(*pos).is_some_and(|byte| *byte != b'\n') {
+                    *pos += 1;
+                }
+            }
+            _ => break,
+        }
+    }
+    let start = *pos;
+    while data.get(*pos).is_some_and(|byte| !byte.is_ascii_whitespace()) {
+        *pos += 1;
+    }
+    Some(&data[start..*pos])
+}
+
+fn pgm_number(data: &[u8], pos: &mut usize) -> Result<usize, CameraImageError> {
+    pgm_token(data, pos)
+        .and_then(|token| std::str::from_utf8(token).ok())
+        .and_then(|token| token.parse().ok())
+        .ok_or(CameraImageError::Format)
+}
+
+/* The Pocket Camera's MAC-GBD controller, with 1 MiB of ROM, 128 KiB of RAM
+ * and an M64282FP image sensor:
+ *
+ * 0x0000-0x1FFF  RAM write enable, 0x0A turns it on. RAM reads always work.
+ * 0x2000-0x3FFF  ROM bank at 0x4000-0x7FFF, 6 bits
+ * 0x4000-0x5FFF  RAM bank, 4 bits. Bit 4 maps the sensor registers at
+ *                0xA000-0xBFFF instead, repeating every 0x80 bytes.
+ *
+ * Sensor registers, all write only except for A000:
+ *
+ * 0xA000  Bit 0 starts a capture and reads 1 while one is running
+ * 0xA001  Bits 0-4 gain, bits 5-6 edge enhancement direction
+ * 0xA002  Exposure time, high byte
+ * 0xA003  Exposure time, low byte
+ * 0xA004  Bits 4-6 edge enhancement ratio, bit 3 inverts the image
+ * 0xA005  Output voltage offsets, not emulated
+ * 0xA006-0xA035  4 x 4 dithering matrix, 3 thresholds per pixel
+ *
+ * Captures finish instantly, so the busy bit is never seen set.
+ */
+#[derive(Debug)]
+pub struct PocketCamera {
+    rom: Vec<u8>,
+    ram: Vec<u8>,
+    ram_enabled: bool,
+    rom_bank: u8,
+    ram_bank: u8,
+    registers_mapped: bool,
+    registers: [u8; REGISTERS],
+    image: CameraImage,
+}
+
+impl PocketCamera {
+    pub fn new(rom: Vec<u8>) -> PocketCamera {
+        PocketCamera {
+            rom,
+            ram: vec![0; RAM_SIZE],
+            ram_enabled: false,
+            rom_bank: 0x01,
+            ram_bank: 0x00,
+            registers_mapped: false,
+            registers: [0; REGISTERS],
+            image: CameraImage::default(),
+        }
+    }
+
+    /* The sensor's reading for one pixel, before dithering. Gain and
+     * exposure are scaled so a gain of 0 and an exposure of 0x1000 pass the
+     * image straight through. Gain steps are 1 dB apart.
+     */
+    fn sensor(&self, x: usize, y: usize) -> f32 {
+        let gain = 10f32.powf((self.registers[1] & 0x1F) as f32 / 20.0);
+        let exposure = u16::from_be_bytes([self.registers[2], self.registers[3]]) as f32;
+        let val = (self.image.pixel(x, y) as f32 * gain * exposure / 4096.0).min(255.0);
+        if self.registers[4] & 0x08 != 0 { 255.0 - val } else { val }
+    }
+
+    /* Sharpens along the rows, the columns or both by subtracting the
+     * neighbouring pixels, which are clamped at the borders.
+     */
+    fn enhanced(&self, x: usize, y: usize) -> f32 {
+        let val = self.sensor(x, y);
+        let ratio = EDGE_RATIOS[(self.registers[4] >> 4 & 0x07) as usize];
+        let mut edge = 0.0;
+        if self.registers[1] & 0x20 != 0 {
+            edge += 2.0 * val;
+            edge -= self.sensor(x.saturating_sub(1), y);
+            edge -= self.sensor((x + 1).min(IMAGE_WIDTH - 1), y);
+        }
+        if self.registers[1] & 0x40 != 0 {
+            edge += 2.0 * val;
+            edge -= self.sensor(x, y.saturating_sub(1));
+            edge -= self.sensor(x, (y + 1).min(IMAGE_HEIGHT - 1));
+        }
+        val + edge * ratio
+    }
+
+    /* Picks one of the 4 shades by comparing against the thresholds for
+     * this pixel's place in the dithering matrix. 3 is black.
+     */
+    fn dither(&self, x: usize, y: usize, val: f32) -> u8 {
+        let cell = DITHER_MATRIX + ((y % 4) * 4 + x % 4) * 3;
+        let thresholds = &self.registers[cell..cell + 3];
+        match thresholds.iter().position(|threshold| val < *threshold as f32) {
+            Some(darkness) => 3 - darkness as u8,
+            None => 0,
+        }
+    }
+
+    /* Takes a picture and stores it in RAM as 2bpp tiles, ready for the
+     * camera ROM to copy to VRAM.
+     */
+    fn capture(&mut self) {
+        for y in 0..IMAGE_HEIGHT {
+            for x in 0..IMAGE_WIDTH {
+                let shade = self.dither(x, y, self.enhanced(x, y));
+                let tile = (y / 8) * (IMAGE_WIDTH / 8) + x / 8;
+                let row = IMAGE_OFFSET + tile * 16 + (y % 8) * 2;
+                let bit = 0x80 >> (x % 8);
+                for plane in 0..2 {
+                    if shade >> plane & 1 != 0 {
+                        self.ram[row + plane] |= bit;
+                    } else {
+                        self.ram[row + plane] &= !bit;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl MemoryBankController for PocketCamera {
+    fn read_rom(&self, address: u16) -> u8 {
+        match address {
+            0x0000..=0x3FFF => read_rom_bank(&self.rom, 0, address),
+            _ => read_rom_bank(&self.rom, self.rom_bank as usize, address),
+        }
+    }
+
+    fn write_rom(&mut self, address: u16, val: u8) {
+        match address {
+            0x0000..=0x1FFF => self.ram_enabled = val & 0x0F == 0x0A,
+            0x2000..=0x3FFF => self.rom_bank = val & 0x3F,
+            0x4000..=0x5FFF => {
+                self.registers_mapped = val & 0x10 != 0;
+                self.ram_bank = val & 0x0F;
+            }
+            _ => {}
+        }
+    }
+
+    fn read_ram(&self, address: u16) -> u8 {
+        if !self.registers_mapped {
+            return match ram_bank_offset(&self.ram, self.ram_bank as usize, address) {
+                Some(offset) => self.ram[offset],
+                None => 0xFF,
+            };
+        }
+        match address & 0x7F {
+            0x00 => self.registers[0] & 0x07,
+            _ => 0x00,
+        }
+    }
+
+    fn write_ram(&mut self, address: u16, val: u8) {
+        if !self.registers_mapped {
+            match ram_bank_offset(&self.ram, self.ram_bank as usize, address) {
+                Some(offset) if self.ram_enabled => self.ram[offset] = val,
+                _ => {}
+            }
+            return;
+        }
+        let register = (address & 0x7F) as usize;
+        if register >= REGISTERS {
+            return;
+        }
+        self.registers[register] = val;
+        if register == 0 && val & 0x01 != 0 {
+            self.capture();
+            self.registers[0] &= !0x01;
+        }
+    }
+
+    fn set_camera_image(&mut self, image: CameraImage) {
+        self.image = image;
+    }
+
+    fn save_data(&mut self) -> Vec<u8> {
+        self.ram.clone()
+    }
+
+    fn load_save_data(&mut self, data: &[u8]) {
+        load_ram(&mut self.ram, data);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::gb::infrared::{Disconnected, IrChannel};
+use crate::gb::mbc::{load_ram, ram_bank_offset, read_rom_bank, MemoryBankController};
+
+/* Hudson's HuC1 is close to MBC1, but swaps RAM enable for a switch between
+ * RAM and an infrared port at 0xA000-0xBFFF:
+ *
+ * 0x0000-0x1FFF  0x0E maps the IR port, anything else maps RAM
+ * 0x2000-0x3FFF  ROM bank at 0x4000-0x7FFF, 6 bits
+ * 0x4000-0x5FFF  RAM bank, 2 bits
+ *
+ * With the IR port mapped, reads give 0xC1 while light is coming in and
+ * 0xC0 otherwise, and bit 0 of writes drives the LED.
+ */
+#[derive(Debug)]
+pub struct HuC1 {
+    rom: Vec<u8>,
+    ram: Vec<u8>,
+    ir: Box<dyn IrChannel>,
+    ir_mapped: bool,
+    rom_bank: u8,
+    ram_bank: u8,
+}
+
+impl HuC1 {
+    pub fn new(rom: Vec<u8>, ram_size: usize) -> HuC1 {
+        HuC1 {
+            rom,
+            ram: vec![0; ram_size],
+            ir: Box::new(Disconnected),
+            ir_mapped: false,
+            rom_bank: 0x01,
+            ram_bank: 0x00,
+        }
+    }
+}
+
+impl MemoryBankController for HuC1 {
+    fn read_rom(&self, address: u16) -> u8 {
+        match address {
+            0x0000..=0x3FFF => read_rom_bank(&self.rom, 0, address),
+            _ => read_rom_bank(&self.rom, self.rom_bank as usize, address),
+        }
+    }
+
+    fn write_rom(&mut self, address: u16, val: u8) {
+        match address {
+            0x0000..=0x1FFF => self.ir_mapped = val == 0x0E,
+            0x2000..=0x3FFF => self.rom_bank = val & 0x3F,
+            0x4000..=0x5FFF => self.ram_bank = val & 0x03,
+            _ => {}
+        }
+    }
+
+    fn read_ram(&self, address: u16) -> u8 {
+        if self.ir_mapped {
+            return 0xC0 | self.ir.receiving() as u8;
+        }
+        match ram_bank_offset(&self.ram, self.ram_bank as usize, address) {
+            Some(offset) => self.ram[offset],
+            None => 0xFF,
+        }
+    }
+
+    fn write_ram(&mut self, address: u16, val: u8) {
+        if self.ir_mapped {
+            self.ir.set_led(val & 0x01 != 0);
+        } else if let Some(offset) = ram_bank_offset(&self.ram, self.ram_bank as usize, address) {
+            self.ram[offset] = val;
+        }
+    }
+
+    fn set_ir_channel(&mut self, channel: Box<dyn IrChannel>) {
+        self.ir = channel;
+    }
+
+    fn save_data(&mut self) -> Vec<u8> {
+        self.ram.clone()
+    }
+
+    fn load_save_data(&mut self, data: &[u8]) {
+        load_ram(&mut self.ram, data);
+    }
+}
@@ -0,0 +1,222 @@
+use crate::gb::infrared::{Disconnected, IrChannel};
+use crate::gb::mbc::rtc::Clock;
+use crate::gb::mbc::{load_ram, ram_bank_offset, read_rom_bank, MemoryBankController};
+
+const MINUTES_PER_DAY: u64 = 1440;
+// Size of the clock state saved after the RAM, laid out like SameBoy's.
+const CLOCK_SAVE_SIZE: usize = 17;
+
+/* Hudson's HuC3 adds a clock, an IR port and a piezo speaker to MBC-style
+ * banking. The low nibble of writes to 0x0000-0x1FFF picks what is mapped
+ * at 0xA000-0xBFFF:
+ *
+ * 0x0  RAM, read only
+ * 0xA  RAM
+ * 0xB  Command register, write only
+ * 0xC  Command response, read only
+ * 0xD  Status, reads 1 once the last command is done
+ * 0xE  IR port, like HuC1's
+ *
+ * 0x2000-0x3FFF is the ROM bank and 0x4000-0x5FFF the RAM bank.
+ *
+ * Commands are one byte, the command in bits 4-6 and an argument in bits
+ * 0-3. Clock and alarm state lives in a little nibble-addressed memory:
+ *
+ * 0x1  Read the nibble at the access index into the response, move on
+ * 0x2  Write the argument to the nibble at the access index
+ * 0x3  Same, then move on
+ * 0x4  Set the low nibble of the access index
+ * 0x5  Set the high nibble of the access index
+ * 0x6  Speaker command, the argument picks the tone
+ *
+ * Index 0x00-0x02 holds the minute of the day, 0x03-0x06 the day counter,
+ * 0x58-0x5A and 0x5B-0x5E the alarm and 0x5F its enable bit.
+ */
+#[derive(Debug)]
+pub struct HuC3 {
+    rom: Vec<u8>,
+    ram: Vec<u8>,
+    ir: Box<dyn IrChannel>,
+    clock: Box<dyn Clock>,
+    mode: u8,
+    rom_bank: u8,
+    ram_bank: u8,
+    minutes: u16,
+    days: u16,
+    alarm_minutes: u16,
+    alarm_days: u16,
+    alarm_enabled: bool,
+    // When `minutes` and `days` were last brought up to date.
+    updated: u64,
+    access_index: u8,
+    response: u8,
+    // The last tone the game asked the speaker to play.
+    tone: Option<u8>,
+}
+
+impl HuC3 {
+    pub fn new(rom: Vec<u8>, ram_size: usize, clock: Box<dyn Clock>) -> HuC3 {
+        let updated = clock.now();
+        HuC3 {
+            rom,
+            ram: vec![0; ram_size],
+            ir: Box::new(Disconnected),
+            clock,
+            mode: 0x00,
+            rom_bank: 0x01,
+            ram_bank: 0x00,
+            minutes: 0,
+            days: 0,
+            alarm_minutes: 0,
+            alarm_days: 0,
+            alarm_enabled: false,
+            updated,
+            access_index: 0x00,
+            response: 0x00,
+            tone: None,
+        }
+    }
+
+    /* The speaker isn't synthesized, but a frontend can play something for
+     * the last speaker command the game sent.
+     */
+    pub fn tone(&self) -> Option<u8> {
+        self.tone
+    }
+
+    /* Catches the clock up on whole minutes. Leftover seconds carry over to
+     * the next update.
+     */
+    fn update_clock(&mut self) {
+        let now = self.clock.now();
+        let elapsed = now.saturating_sub(self.updated) / 60;
+        self.updated += elapsed * 60;
+        let minutes = self.minutes as u64 + elapsed;
+        self.minutes = (minutes % MINUTES_PER_DAY) as u16;
+        self.days = self.days.wrapping_add((minutes / MINUTES_PER_DAY) as u16);
+    }
+
+    fn read_nibble(&self, index: u8) -> u8 {
+        let nibble = |val: u16, n: u8| (val >> (n * 4) & 0x0F) as u8;
+        match index {
+            0x00..=0x02 => nibble(self.minutes, index),
+            0x03..=0x06 => nibble(self.days, index - 0x03),
+            0x58..=0x5A => nibble(self.alarm_minutes, index - 0x58),
+            0x5B..=0x5E => nibble(self.alarm_days, index - 0x5B),
+            0x5F => self.alarm_enabled as u8,
+            _ => 0x00,
+        }
+    }
+
+    fn write_nibble(&mut self, index: u8, val: u8) {
+        let set = |word: &mut u16, n: u8| {
+            *word = *word & !(0x0F << (n * 4)) | (val as u16 & 0x0F) << (n * 4);
+        };
+        match index {
+            0x00..=0x02 => set(&mut self.minutes, index),
+            0x03..=0x06 => set(&mut self.days, index - 0x03),
+            0x58..=0x5A => set(&mut self.alarm_minutes, index - 0x58),
+            0x5B..=0x5E => set(&mut self.alarm_days, index - 0x5B),
+            0x5F => self.alarm_enabled = val & 0x01 != 0,
+            _ => {}
+        }
+    }
+
+    fn command(&mut self, val: u8) {
+        let arg = val & 0x0F;
+        self.update_clock();
+        match val >> 4 & 0x07 {
+            0x1 => {
+                self.response = self.read_nibble(self.access_index);
+                self.access_index = self.access_index.wrapping_add(1);
+            }
+            0x2 => self.write_nibble(self.access_index, arg),
+            0x3 => {
+                self.write_nibble(self.access_index, arg);
+                self.access_index = self.access_index.wrapping_add(1);
+            }
+            0x4 => self.access_index = self.access_index & 0xF0 | arg,
+            0x5 => self.access_index = self.access_index & 0x0F | arg << 4,
+            0x6 => self.tone = Some(arg),
+            _ => {}
+        }
+    }
+}
+
+impl MemoryBankController for HuC3 {
+    fn read_rom(&self, address: u16) -> u8 {
+        match address {
+            0x0000..=0x3FFF => read_rom_bank(&self.rom, 0, address),
+            _ => read_rom_bank(&self.rom, self.rom_bank as usize, address),
+        }
+    }
+
+    fn write_rom(&mut self, address: u16, val: u8) {
+        match address {
+            0x0000..=0x1FFF => self.mode = val & 0x0F,
+            0x2000..=0x3FFF => self.rom_bank = val & 0x7F,
+            0x4000..=0x5FFF => self.ram_bank = val & 0x03,
+            _ => {}
+        }
+    }
+
+    fn read_ram(&self, address: u16) -> u8 {
+        match self.mode {
+            0x00 | 0x0A => match ram_bank_offset(&self.ram, self.ram_bank as usize, address) {
+                Some(offset) => self.ram[offset],
+                None => 0xFF,
+            },
+            0x0C => self.response,
+            0x0D => 0x01,
+            0x0E => 0xC0 | self.ir.receiving() as u8,
+            _ => 0xFF,
+        }
+    }
+
+    fn write_ram(&mut self, address: u16, val: u8) {
+        match self.mode {
+            0x0A => {
+                if let Some(offset) = ram_bank_offset(&self.ram, self.ram_bank as usize, address) {
+                    self.ram[offset] = val;
+                }
+            }
+            0x0B => self.command(val),
+            0x0E => self.ir.set_led(val & 0x01 != 0),
+            _ => {}
+        }
+    }
+
+    fn set_ir_channel(&mut self, channel: Box<dyn IrChannel>) {
+        self.ir = channel;
+    }
+
+    /* The RAM, then the time it was saved at and the clock and alarm, all
+     * little-endian.
+     */
+    fn save_data(&mut self) -> Vec<u8> {
+        self.update_clock();
+        let mut data = self.ram.clone();
+        data.extend(self.updated.to_le_bytes());
+        for word in [self.minutes, self.days, self.alarm_minutes, self.alarm_days] {
+            data.extend(word.to_le_bytes());
+        }
+        data.push(self.alarm_enabled as u8);
+        data
+    }
+
+    /* The clock catches up on the time passed since the save was written. */
+    fn load_save_data(&mut self, data: &[u8]) {
+        let ram_size = load_ram(&mut self.ram, data);
+        let Some(clock) = data[ram_size..].get(..CLOCK_SAVE_SIZE) else {
+            return;
+        };
+        let word = |n: usize| u16::from_le_bytes([clock[8 + n * 2], clock[9 + n * 2]]);
+        self.updated = u64::from_le_bytes(clock[..8].try_into().unwrap());
+        self.minutes = word(0);
+        self.days = word(1);
+        self.alarm_minutes = word(2);
+        self.alarm_days = word(3);
+        self.alarm_enabled = clock[16] != 0;
+        self.update_clock();
+    }
+}
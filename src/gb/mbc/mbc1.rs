@@ -0,0 +1,118 @@
+use crate::gb::cartridge::NINTENDO_LOGO;
+use crate::gb::mbc::{
+    load_ram, ram_bank_offset, read_rom_bank, MemoryBankController, ROM_BANK_SIZE,
+};
+
+/* MBC1, the controller in most early games. Up to 2 MiB of ROM and 32 KiB of
+ * RAM, switched through two bank registers:
+ *
+ * 0x0000-0x1FFF  RAM enable, 0xA in the low nibble turns it on
+ * 0x2000-0x3FFF  BANK1, the low 5 bits of the ROM bank at 0x4000-0x7FFF
+ * 0x4000-0x5FFF  BANK2, 2 more bits for the ROM bank or the RAM bank
+ * 0x6000-0x7FFF  Mode. In mode 1 BANK2 also applies to 0x0000-0x3FFF and
+ *                to RAM, in mode 0 those are stuck on bank 0.
+ *
+ * MBC1M multicarts wire BANK2 one bit lower, skipping the top bit of BANK1,
+ * so it picks one of four 256 KiB games.
+ */
+#[derive(Debug)]
+pub struct Mbc1 {
+    rom: Vec<u8>,
+    ram: Vec<u8>,
+    ram_enabled: bool,
+    bank1: u8,
+    bank2: u8,
+    mode: bool,
+    multicart: bool,
+}
+
+impl Mbc1 {
+    pub fn new(rom: Vec<u8>, ram_size: usize, multicart: bool) -> Mbc1 {
+        Mbc1 {
+            rom,
+            ram: vec![0; ram_size],
+            ram_enabled: false,
+            bank1: 0x01,
+            bank2: 0x00,
+            mode: false,
+            multicart,
+        }
+    }
+
+    /* There is nothing in the header to tell MBC1M apart from MBC1. Every
+     * multicart is 1 MiB and each of its games starts with a header of its
+     * own, so look for a second Nintendo logo at the start of game two.
+     */
+    pub fn detect_multicart(rom: &[u8]) -> bool {
+        let logo = 0x10 * ROM_BANK_SIZE + 0x0104;
+        rom.len() == 0x100000 && rom[logo..logo + NINTENDO_LOGO.len()] == NINTENDO_LOGO
+    }
+
+    fn bank2_shift(&self) -> u8 {
+        if self.multicart { 4 } else { 5 }
+    }
+
+    fn rom_bank_low(&self) -> usize {
+        if self.mode {
+            (self.bank2 << self.bank2_shift()) as usize
+        } else {
+            0
+        }
+    }
+
+    fn rom_bank_high(&self) -> usize {
+        let bank1 = if self.multicart { self.bank1 & 0x0F } else { self.bank1 };
+        (self.bank2 << self.bank2_shift() | bank1) as usize
+    }
+
+    /* Offset into the RAM chip, wrapping on carts with less than 32 KiB. */
+    fn ram_offset(&self, address: u16) -> Option<usize> {
+        if !self.ram_enabled {
+            return None;
+        }
+        let bank = if self.mode { self.bank2 as usize } else { 0 };
+        ram_bank_offset(&self.ram, bank, address)
+    }
+}
+
+impl MemoryBankController for Mbc1 {
+    fn read_rom(&self, address: u16) -> u8 {
+        match address {
+            0x0000..=0x3FFF => read_rom_bank(&self.rom, self.rom_bank_low(), address),
+            _ => read_rom_bank(&self.rom, self.rom_bank_high(), address),
+        }
+    }
+
+    fn write_rom(&mut self, address: u16, val: u8) {
+        match address {
+            0x0000..=0x1FFF => self.ram_enabled = val & 0x0F == 0x0A,
+            // Bank 0 can't be selected here, it turns into bank 1. The check
+            // looks at all 5 bits, so banks 0x20, 0x40 and 0x60 can't be
+            // reached through 0x4000-0x7FFF either.
+            0x2000..=0x3FFF => self.bank1 = if val & 0x1F == 0 { 0x01 } else { val & 0x1F },
+            0x4000..=0x5FFF => self.bank2 = val & 0x03,
+            _ => self.mode = val & 0x01 != 0,
+        }
+    }
+
+    fn read_ram(&self, address: u16) -> u8 {
+        match self.ram_offset(address) {
+            Some(offset) => self.ram[offset],
+            None => 0xFF,
+        }
+    }
+
+    fn write_ram(&mut self, address: u16, val: u8) {
+        if let Some(offset) = self.ram_offset(address) {
+            self.ram[offset] = val;
+        }
+    }
+
+    fn save_data(&mut self) -> Vec<u8> {
+        self.ram.clone()
+    }
+
+    fn load_save_data(&mut self, data: &[u8]) {
+        load_ram(&mut self.ram, data);
+    }
+}
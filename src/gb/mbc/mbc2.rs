@@ -0,0 +1,77 @@
+use crate::gb::mbc::{load_ram, read_rom_bank, MemoryBankController};
+
+/* MBC2 has up to 256 KiB of ROM and its own 512 x 4-bit RAM. It only has
+ * one register range, 0x0000-0x3FFF, and address bit 8 decides which
+ * register a write goes to:
+ *
+ * bit 8 clear  RAM enable, 0xA in the low nibble turns it on
+ * bit 8 set    ROM bank at 0x4000-0x7FFF, 4 bits, bank 0 turns into 1
+ */
+#[derive(Debug)]
+pub struct Mbc2 {
+    rom: Vec<u8>,
+    ram: [u8; 0x200],
+    ram_enabled: bool,
+    rom_bank: u8,
+}
+
+impl Mbc2 {
+    pub fn new(rom: Vec<u8>) -> Mbc2 {
+        Mbc2 {
+            rom,
+            ram: [0; 0x200],
+            ram_enabled: false,
+            rom_bank: 0x01,
+        }
+    }
+}
+
+impl MemoryBankController for Mbc2 {
+    fn read_rom(&self, address: u16) -> u8 {
+        match address {
+            0x0000..=0x3FFF => read_rom_bank(&self.rom, 0, address),
+            _ => read_rom_bank(&self.rom, self.rom_bank as usize, address),
+        }
+    }
+
+    fn write_rom(&mut self, address: u16, val: u8) {
+        match address {
+            0x0000..=0x3FFF if address & 0x0100 == 0 => {
+                self.ram_enabled = val & 0x0F == 0x0A;
+            }
+            0x0000..=0x3FFF => {
+                self.rom_bank = if val & 0x0F == 0 { 0x01 } else { val & 0x0F };
+            }
+            _ => {}
+        }
+    }
+
+    /* Only the low 9 address bits reach the RAM, so its 512 bytes repeat
+     * all over 0xA000-0xBFFF. Only the low nibble of each byte exists, the
+     * upper one reads back as 1s.
+     */
+    fn read_ram(&self, address: u16) -> u8 {
+        if !self.ram_enabled {
+            return 0xFF;
+        }
+        0xF0 | self.ram[(address & 0x01FF) as usize]
+    }
+
+    fn write_ram(&mut self, address: u16, val: u8) {
+        if self.ram_enabled {
+            self.ram[(address & 0x01FF) as usize] = val & 0x0F;
+        }
+    }
+
+    /* One nibble per byte, like the RAM itself. */
+    fn save_data(&mut self) -> Vec<u8> {
+        self.ram.to_vec()
+    }
+
+    fn load_save_data(&mut self, data: &[u8]) {
+        load_ram(&mut self.ram, data);
+        for byte in &mut self.ram {
+            *byte &= 0x0F;
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use crate::gb::mbc::rtc::{Clock, Rtc};
+use crate::gb::mbc::{load_ram, ram_bank_offset, read_rom_bank, MemoryBankController};
+
+/* MBC3 has up to 2 MiB of ROM, 32 KiB of RAM and, on some boards, a
+ * real-time clock:
+ *
+ * 0x0000-0x1FFF  RAM and clock enable, 0xA in the low nibble turns them on
+ * 0x2000-0x3FFF  ROM bank at 0x4000-0x7FFF, 7 bits, bank 0 turns into 1
+ * 0x4000-0x5FFF  0x00-0x03 maps a RAM bank at 0xA000-0xBFFF,
+ *                0x08-0x0C maps a clock register there instead
+ * 0x6000-0x7FFF  Clock latch, see Rtc::write_latch
+ */
+#[derive(Debug)]
+pub struct Mbc3 {
+    rom: Vec<u8>,
+    ram: Vec<u8>,
+    rtc: Option<Rtc>,
+    ram_enabled: bool,
+    rom_bank: u8,
+    // A RAM bank, or a clock register from 0x08 up.
+    ram_select: u8,
+}
+
+impl Mbc3 {
+    /* Boards with a clock get a time source, the rest pass None. */
+    pub fn new(rom: Vec<u8>, ram_size: usize, clock: Option<Box<dyn Clock>>) -> Mbc3 {
+        Mbc3 {
+            rom,
+            ram: vec![0; ram_size],
+            rtc: clock.map(Rtc::new),
+            ram_enabled: false,
+            rom_bank: 0x01,
+            ram_select: 0x00,
+        }
+    }
+
+    fn ram_offset(&self, address: u16) -> Option<usize> {
+        if !self.ram_enabled {
+            return None;
+        }
+        ram_bank_offset(&self.ram, self.ram_select as usize, address)
+    }
+}
+
+impl MemoryBankController for Mbc3 {
+    fn read_rom(&self, address: u16) -> u8 {
+        match address {
+            0x0000..=0x3FFF => read_rom_bank(&self.rom, 0, address),
+            _ => read_rom_bank(&self.rom, self.rom_bank as usize, address),
+        }
+    }
+
+    fn write_rom(&mut self, address: u16, val: u8) {
+        match address {
+            0x0000..=0x1FFF => self.ram_enabled = val & 0x0F == 0x0A,
+            0x2000..=0x3FFF => self.rom_bank = if val & 0x7F == 0 { 0x01 } else { val & 0x7F },
+            0x4000..=0x5FFF => self.ram_select = val & 0x0F,
+            _ => {
+                if let Some(rtc) = &mut self.rtc {
+                    rtc.write_latch(val);
+                }
+            }
+        }
+    }
+
+    fn read_ram(&self, address: u16) -> u8 {
+        match (self.ram_select, &self.rtc) {
+            _ if !self.ram_enabled => 0xFF,
+            (0x08..=0x0C, Some(rtc)) => rtc.read(self.ram_select),
+            (0x00..=0x03, _) => match self.ram_offset(address) {
+                Some(offset) => self.ram[offset],
+                None => 0xFF,
+            },
+            _ => 0xFF,
+        }
+    }
+
+    fn write_ram(&mut self, address: u16, val: u8) {
+        if !self.ram_enabled {
+            return;
+        }
+        match (self.ram_select, &mut self.rtc) {
+            (0x08..=0x0C, Some(rtc)) => rtc.write(self.ram_select, val),
+            (0x00..=0x03, _) => {
+                if let Some(offset) = self.ram_offset(address) {
+                    self.ram[offset] = val;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /* The RAM, then the clock if there is one. Loading a save lets the
+     * clock catch up on the time that has passed since it was written.
+     */
+    fn save_data(&mut self) -> Vec<u8> {
+        let mut data = self.ram.clone();
+        if let Some(rtc) = &mut self.rtc {
+            data.extend(rtc.save());
+        }
+        data
+    }
+
+    fn load_save_data(&mut self, data: &[u8]) {
+        let ram_size = load_ram(&mut self.ram, data);
+        if let Some(rtc) = &mut self.rtc {
+            rtc.load(&data[ram_size..]);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use crate::gb::mbc::{
+    load_ram, ram_bank_offset, read_rom_bank, MemoryBankController, RumbleListener,
+};
+use std::fmt;
+
+/* MBC5 has up to 8 MiB of ROM and 128 KiB of RAM:
+ *
+ * 0x0000-0x1FFF  RAM enable, only exactly 0x0A turns it on
+ * 0x2000-0x2FFF  Low 8 bits of the ROM bank at 0x4000-0x7FFF
+ * 0x3000-0x3FFF  Bit 8 of the ROM bank
+ * 0x4000-0x5FFF  RAM bank, 4 bits. Rumble boards use bit 3 for the motor
+ *                instead, leaving 3 bits for the RAM bank.
+ *
+ * Unlike the older controllers, bank 0 can be mapped at 0x4000-0x7FFF.
+ */
+pub struct Mbc5 {
+    rom: Vec<u8>,
+    ram: Vec<u8>,
+    ram_enabled: bool,
+    rom_bank: u16,
+    ram_bank: u8,
+    rumble: Option<bool>,
+    rumble_listener: Option<RumbleListener>,
+}
+
+impl fmt::Debug for Mbc5 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Mbc5")
+            .field("ram_enabled", &self.ram_enabled)
+            .field("rom_bank", &self.rom_bank)
+            .field("ram_bank", &self.ram_bank)
+            .field("rumble", &self.rumble)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Mbc5 {
+    pub fn new(rom: Vec<u8>, ram_size: usize, rumble: bool) -> Mbc5 {
+        Mbc5 {
+            rom,
+            ram: vec![0; ram_size],
+            ram_enabled: false,
+            rom_bank: 0x001,
+            ram_bank: 0x00,
+            rumble: rumble.then_some(false),
+            rumble_listener: None,
+        }
+    }
+
+    /* Whether the motor is running, or None if the board has no motor. */
+    pub fn rumble(&self) -> Option<bool> {
+        self.rumble
+    }
+
+    fn ram_offset(&self, address: u16) -> Option<usize> {
+        if !self.ram_enabled {
+            return None;
+        }
+        ram_bank_offset(&self.ram, self.ram_bank as usize, address)
+    }
+
+    fn write_ram_bank(&mut self, val: u8) {
+        let Some(rumble) = self.rumble else {
+            self.ram_bank = val & 0x0F;
+            return;
+        };
+        self.ram_bank = val & 0x07;
+        let motor = val & 0x08 != 0;
+        if motor != rumble {
+            self.rumble = Some(motor);
+            if let Some(listener) = &mut self.rumble_listener {
+                listener(motor);
+            }
+        }
+    }
+}
+
+impl MemoryBankController for Mbc5 {
+    fn read_rom(&self, address: u16) -> u8 {
+        match address {
+            0x0000..=0x3FFF => read_rom_bank(&self.rom, 0, address),
+            _ => read_rom_bank(&self.rom, self.rom_bank as usize, address),
+        }
+    }
+
+    fn write_rom(&mut self, address: u16, val: u8) {
+        match address {
+            0x0000..=0x1FFF => self.ram_enabled = val == 0x0A,
+            0x2000..=0x2FFF => self.rom_bank = self.rom_bank & 0x100 | val as u16,
+            0x3000..=0x3FFF => self.rom_bank = self.rom_bank & 0x0FF | ((val & 0x01) as u16) << 8,
+            0x4000..=0x5FFF => self.write_ram_bank(val),
+            _ => {}
+        }
+    }
+
+    fn read_ram(&self, address: u16) -> u8 {
+        match self.ram_offset(address) {
+            Some(offset) => self.ram[offset],
+            None => 0xFF,
+        }
+    }
+
+    fn write_ram(&mut self, address: u16, val: u8) {
+        if let Some(offset) = self.ram_offset(address) {
+            self.ram[offset] = val;
+        }
+    }
+
+    fn set_rumble_listener(&mut self, listener: RumbleListener) {
+        self.rumble_listener = Some(listener);
+    }
+
+    fn save_data(&mut self) -> Vec<u8> {
+        self.ram.clone()
+    }
+
+    fn load_save_data(&mut self, data: &[u8]) {
+        load_ram(&mut self.ram, data);
+    }
+}
@@ -0,0 +1,263 @@
+use crate::gb::mbc::{read_rom_bank, MemoryBankController};
+
+// Accelerometer readings are centred on this, and move by about 0x70 per g.
+const TILT_CENTER: u16 = 0x81D0;
+const TILT_PER_G: f32 = 112.0;
+
+/* MBC7, found in Kirby Tilt 'n' Tumble and Command Master. Instead of RAM it
+ * has a two-axis accelerometer and a 93LC56 serial EEPROM, both behind
+ * registers at 0xA000-0xAFFF:
+ *
+ * 0x0000-0x1FFF  First register enable, 0x0A turns it on
+ * 0x2000-0x3FFF  ROM bank at 0x4000-0x7FFF
+ * 0x4000-0x5FFF  Second register enable, 0x40 turns it on
+ *
+ * Both enables have to be set. Address bits 4-7 pick the register:
+ *
+ * 0xAx0x  Write 0x55 to clear the latched accelerometer values
+ * 0xAx1x  Then write 0xAA to latch the current ones
+ * 0xAx2x  X low byte
+ * 0xAx3x  X high byte
+ * 0xAx4x  Y low byte
+ * 0xAx5x  Y high byte
+ * 0xAx6x  Always 0x00
+ * 0xAx8x  EEPROM pins: bit 7 is CS, bit 6 CLK, bit 1 DI and bit 0 DO
+ */
+#[derive(Debug)]
+pub struct Mbc7 {
+    rom: Vec<u8>,
+    rom_bank: u8,
+    enable1: bool,
+    enable2: bool,
+    tilt: (u16, u16),
+    latched: (u16, u16),
+    latch_cleared: bool,
+    eeprom: Eeprom,
+}
+
+impl Mbc7 {
+    pub fn new(rom: Vec<u8>) -> Mbc7 {
+        Mbc7 {
+            rom,
+            rom_bank: 0x01,
+            enable1: false,
+            enable2: false,
+            tilt: (TILT_CENTER, TILT_CENTER),
+            latched: (0x8000, 0x8000),
+            latch_cleared: false,
+            eeprom: Eeprom::new(),
+        }
+    }
+
+    fn registers_enabled(&self) -> bool {
+        self.enable1 && self.enable2
+    }
+}
+
+impl MemoryBankController for Mbc7 {
+    fn read_rom(&self, address: u16) -> u8 {
+        match address {
+            0x0000..=0x3FFF => read_rom_bank(&self.rom, 0, address),
+            _ => read_rom_bank(&self.rom, self.rom_bank as usize, address),
+        }
+    }
+
+    fn write_rom(&mut self, address: u16, val: u8) {
+        match address {
+            0x0000..=0x1FFF => self.enable1 = val == 0x0A,
+            0x2000..=0x3FFF => self.rom_bank = val & 0x7F,
+            0x4000..=0x5FFF => self.enable2 = val == 0x40,
+            _ => {}
+        }
+    }
+
+    fn read_ram(&self, address: u16) -> u8 {
+        if !self.registers_enabled() || address >= 0xB000 {
+            return 0xFF;
+        }
+        match address & 0x00F0 {
+            0x00 | 0x10 => 0xFF,
+            0x20 => self.latched.0 as u8,
+            0x30 => (self.latched.0 >> 8) as u8,
+            0x40 => self.latched.1 as u8,
+            0x50 => (self.latched.1 >> 8) as u8,
+            0x60 => 0x00,
+            0x80 => self.eeprom.read_pins(),
+            _ => 0xFF,
+        }
+    }
+
+    fn write_ram(&mut self, address: u16, val: u8) {
+        if !self.registers_enabled() || address >= 0xB000 {
+            return;
+        }
+        match (address & 0x00F0, val) {
+            (0x00, 0x55) => {
+                self.latched = (0x8000, 0x8000);
+                self.latch_cleared = true;
+            }
+            (0x10, 0xAA) if self.latch_cleared => {
+                self.latched = self.tilt;
+                self.latch_cleared = false;
+            }
+            (0x80, _) => self.eeprom.write_pins(val),
+            _ => {}
+        }
+    }
+
+    /* Tilt in g along each axis. Positive values raise the reading. */
+    fn set_tilt(&mut self, x: f32, y: f32) {
+        let reading = |g: f32| (TILT_CENTER as f32 + g * TILT_PER_G).clamp(0.0, 65535.0) as u16;
+        self.tilt = (reading(x), reading(y));
+    }
+
+    /* The EEPROM's 128 words, each low byte first. */
+    fn save_data(&mut self) -> Vec<u8> {
+        self.eeprom.words.iter().flat_map(|word| word.to_le_bytes()).collect()
+    }
+
+    fn load_save_data(&mut self, data: &[u8]) {
+        for (word, bytes) in self.eeprom.words.iter_mut().zip(data.chunks_exact(2)) {
+            *word = u16::from_le_bytes([bytes[0], bytes[1]]);
+        }
+    }
+}
+
+#[derive(Debug)]
+enum EepromState {
+    // Waiting for a start bit.
+    Idle,
+    // Shifting in the 2-bit opcode and 8-bit address.
+    Command { bits: u16, count: u8 },
+    // Shifting out words, moving on to the next one after each.
+    Reading { address: u8, data: u16, count: u8 },
+    // Shifting in a word for one address, or for all of them.
+    Writing { address: Option<u8>, data: u16, count: u8 },
+}
+
+/* A 93LC56 in 16-bit mode: 128 words read and written over a Microwire
+ * serial bus that the game bit-bangs through the MBC7 registers. Data is
+ * clocked in and out on rising CLK edges while CS is high. Writes finish
+ * instantly, so DO always reports ready outside of a read.
+ */
+#[derive(Debug)]
+struct Eeprom {
+    words: [u16; 128],
+    state: EepromState,
+    write_enabled: bool,
+    cs: bool,
+    clk: bool,
+    di: bool,
+    do_: bool,
+}
+
+impl Eeprom {
+    fn new() -> Eeprom {
+        Eeprom {
+            words: [0xFFFF; 128],
+            state: EepromState::Idle,
+            write_enabled: false,
+            cs: false,
+            clk: false,
+            di: false,
+            do_: true,
+        }
+    }
+
+    fn read_pins(&self) -> u8 {
+        (self.cs as u8) << 7 | (self.clk as u8) << 6 | (self.di as u8) << 1 | self.do_ as u8
+    }
+
+    fn write_pins(&mut self, val: u8) {
+        let cs = val & 0x80 != 0;
+        let clk = val & 0x40 != 0;
+        self.di = val & 0x02 != 0;
+        if !cs {
+            // Dropping CS aborts whatever command was in progress.
+            self.state = EepromState::Idle;
+            self.do_ = true;
+        } else if clk && !self.clk {
+            self.clock_in(self.di);
+        }
+        self.cs = cs;
+        self.clk = clk;
+    }
+
+    fn clock_in(&mut self, bit: bool) {
+        self.state = match self.state {
+            EepromState::Idle if bit => EepromState::Command { bits: 0, count: 0 },
+            EepromState::Idle => EepromState::Idle,
+            EepromState::Command { bits, count } => {
+                let bits = bits << 1 | bit as u16;
+                if count + 1 < 10 {
+                    EepromState::Command { bits, count: count + 1 }
+                } else {
+                    self.command(bits)
+                }
+            }
+            EepromState::Reading { address, data, count } => {
+                self.do_ = data & 0x8000 != 0;
+                if count + 1 < 16 {
+                    EepromState::Reading { address, data: data << 1, count: count + 1 }
+                } else {
+                    let address = (address + 1) & 0x7F;
+                    let data = self.words[address as usize];
+                    EepromState::Reading { address, data, count: 0 }
+                }
+            }
+            EepromState::Writing { address, data, count } => {
+                let data = data << 1 | bit as u16;
+                if count + 1 < 16 {
+                    EepromState::Writing { address, data, count: count + 1 }
+                } else {
+                    self.write(address, data);
+                    EepromState::Idle
+                }
+            }
+        };
+    }
+
+    /* Runs a command once its opcode and address are in. */
+    fn command(&mut self, bits: u16) -> EepromState {
+        let address = (bits & 0x7F) as u8;
+        self.do_ = true;
+        match (bits >> 8, (bits >> 6) & 0x03) {
+            // READ starts with a dummy 0 bit before the data.
+            (0b10, _) => {
+                self.do_ = false;
+                let data = self.words[address as usize];
+                EepromState::Reading { address, data, count: 0 }
+            }
+            (0b01, _) => EepromState::Writing { address: Some(address), data: 0, count: 0 },
+            (0b11, _) => {
+                self.write(Some(address), 0xFFFF);
+                EepromState::Idle
+            }
+            (_, 0b11) => {
+                self.write_enabled = true;
+                EepromState::Idle
+            }
+            (_, 0b00) => {
+                self.write_enabled = false;
+                EepromState::Idle
+            }
+            (_, 0b10) => {
+                self.write(None, 0xFFFF);
+                EepromState::Idle
+            }
+            _ => EepromState::Writing { address: None, data: 0, count: 0 },
+        }
+    }
+
+    /* Writes one word, or every word for None, if writes are enabled. */
+    fn write(&mut self, address: Option<u8>, data: u16) {
+        if !self.write_enabled {
+            return;
+        }
+        match address {
+            Some(address) => self.words[address as usize] = data,
+            None => self.words = [data; 128],
+        }
+        self.do_ = true;
+    }
+}
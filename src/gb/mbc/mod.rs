@@ -0,0 +1,150 @@
+use crate::gb::infrared::IrChannel;
+use std::fmt::Debug;
+
+mod camera;
+mod huc1;
+mod huc3;
+mod mbc1;
+mod mbc2;
+mod mbc3;
+mod mbc5;
+mod mbc7;
+mod rtc;
+
+pub use camera::{CameraImage, CameraImageError, PocketCamera, IMAGE_HEIGHT, IMAGE_WIDTH};
+pub use huc1::HuC1;
+pub use huc3::HuC3;
+pub use mbc1::Mbc1;
+pub use mbc2::Mbc2;
+pub use mbc3::Mbc3;
+pub use mbc5::Mbc5;
+pub use mbc7::Mbc7;
+pub use rtc::{Clock, SystemClock};
+
+const ROM_BANK_SIZE: usize = 0x4000;
+const RAM_BANK_SIZE: usize = 0x2000;
+
+/* Called with true when a cartridge's rumble motor starts and false when it
+ * stops.
+ */
+pub type RumbleListener = Box<dyn FnMut(bool)>;
+
+/* The hardware on the cartridge that sits between the bus and the ROM and
+ * external RAM chips. Addresses are passed through unchanged, so ROM
+ * accesses are in 0x0000-0x7FFF and RAM accesses in 0xA000-0xBFFF.
+ */
+pub trait MemoryBankController: Debug {
+    fn read_rom(&self, address: u16) -> u8;
+
+    /* ROM can't be written to. Controllers decode these writes as commands
+     * instead, e.g. to switch banks or enable RAM.
+     */
+    fn write_rom(&mut self, address: u16, val: u8);
+
+    fn read_ram(&self, address: u16) -> u8;
+
+    fn write_ram(&mut self, address: u16, val: u8);
+
+    /* Lets a frontend drive force feedback. Boards without a rumble motor
+     * never call the listener.
+     */
+    fn set_rumble_listener(&mut self, _listener: RumbleListener) {}
+
+    /* Feeds cartridges with an accelerometer. Others ignore it. */
+    fn set_tilt(&mut self, _x: f32, _y: f32) {}
+
+    /* Connects the IR port of cartridges that have one. */
+    fn set_ir_channel(&mut self, _channel: Box<dyn IrChannel>) {}
+
+    /* Points the camera of cartridges that have one at a still image. */
+    fn set_camera_image(&mut self, _image: CameraImage) {}
+
+    /* What a battery keeps alive while the Game Boy is off, laid out the
+     * way other emulators write .sav files. Whether there is a battery to
+     * begin with is up to the cartridge header.
+     */
+    fn save_data(&mut self) -> Vec<u8> {
+        Vec::new()
+    }
+
+    fn load_save_data(&mut self, _data: &[u8]) {}
+}
+
+/* Fills RAM from the start of a save, which may be shorter or longer than
+ * the RAM. Returns how many bytes were used.
+ */
+fn load_ram(ram: &mut [u8], data: &[u8]) -> usize {
+    let size = ram.len().min(data.len());
+    ram[..size].copy_from_slice(&data[..size]);
+    size
+}
+
+/* Reads from a 16 KiB ROM bank. Bank numbers past the end of the ROM wrap,
+ * as the unused upper address lines aren't connected.
+ */
+fn read_rom_bank(rom: &[u8], bank: usize, address: u16) -> u8 {
+    let banks = (rom.len() / ROM_BANK_SIZE).max(1);
+    let offset = (bank % banks) * ROM_BANK_SIZE + (address as usize % ROM_BANK_SIZE);
+    rom.get(offset).copied().unwrap_or(0xFF)
+}
+
+/* Finds where an access to an 8 KiB RAM bank lands in the RAM. Like ROM
+ * banks, bank numbers past the end of the RAM wrap. None if there is no RAM.
+ */
+fn ram_bank_offset(ram: &[u8], bank: usize, address: u16) -> Option<usize> {
+    if ram.is_empty() {
+        return None;
+    }
+    let offset = bank * RAM_BANK_SIZE + (address as usize % RAM_BANK_SIZE);
+    Some(offset % ram.len())
+}
+
+/* A cartridge with no controller at all: up to 32 KiB of ROM mapped
+ * straight into 0x0000-0x7FFF and optionally up to 8 KiB of RAM at
+ * 0xA000-0xBFFF. Also stands in for an empty cartridge slot, where the bus
+ * floats high.
+ */
+#[derive(Debug, Default)]
+pub struct RomOnly {
+    rom: Vec<u8>,
+    ram: Vec<u8>,
+}
+
+impl RomOnly {
+    pub fn new(rom: Vec<u8>) -> RomOnly {
+        RomOnly::with_ram(rom, 0)
+    }
+
+    pub fn with_ram(rom: Vec<u8>, ram_size: usize) -> RomOnly {
+        RomOnly {
+            rom,
+            ram: vec![0; ram_size.min(RAM_BANK_SIZE)],
+        }
+    }
+}
+
+impl MemoryBankController for RomOnly {
+    fn read_rom(&self, address: u16) -> u8 {
+        self.rom.get(address as usize).copied().unwrap_or(0xFF)
+    }
+
+    fn write_rom(&mut self, _address: u16, _val: u8) {}
+
+    fn read_ram(&self, address: u16) -> u8 {
+        self.ram.get((address - 0xA000) as usize).copied().unwrap_or(0xFF)
+    }
+
+    fn write_ram(&mut self, address: u16, val: u8) {
+        if let Some(byte) = self.ram.get_mut((address - 0xA000) as usize) {
+            *byte = val;
+        }
+    }
+
+    fn save_data(&mut self) -> Vec<u8> {
+        self.ram.clone()
+    }
+
+    fn load_save_data(&mut self, data: &[u8]) {
+        load_ram(&mut self.ram, data);
+    }
+}
@@ -0,0 +1,208 @@
+use std::fmt::Debug;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/* Where real-time clocks on cartridges get the time from, in whole seconds
+ * since the Unix epoch. Tests swap in a clock they can move by hand.
+ */
+pub trait Clock: Debug {
+    fn now(&self) -> u64;
+}
+
+#[derive(Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs())
+    }
+}
+
+// Size of the clock state saved after the cartridge RAM, as BGB and
+// SameBoy do it. VBA-M writes a 4 byte timestamp instead of 8.
+pub const RTC_SAVE_SIZE: usize = 48;
+const RTC_SAVE_SIZE_SHORT: usize = 44;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct RtcRegisters {
+    seconds: u8,
+    minutes: u8,
+    hours: u8,
+    // 9-bit day counter.
+    days: u16,
+    halt: bool,
+    // Set when the day counter overflows, cleared only by the game.
+    carry: bool,
+}
+
+impl RtcRegisters {
+    fn read(&self, register: u8) -> u8 {
+        match register {
+            0x08 => self.seconds,
+            0x09 => self.minutes,
+            0x0A => self.hours,
+            0x0B => self.days as u8,
+            _ => (self.carry as u8) << 7 | (self.halt as u8) << 6 | (self.days >> 8) as u8,
+        }
+    }
+
+    fn write(&mut self, register: u8, val: u8) {
+        match register {
+            0x08 => self.seconds = val & 0x3F,
+            0x09 => self.minutes = val & 0x3F,
+            0x0A => self.hours = val & 0x1F,
+            0x0B => self.days = self.days & 0x100 | val as u16,
+            _ => {
+                self.days = self.days & 0xFF | ((val & 0x01) as u16) << 8;
+                self.halt = val & 0x40 != 0;
+                self.carry = val & 0x80 != 0;
+            }
+        }
+    }
+
+    fn in_range(&self) -> bool {
+        self.seconds < 60 && self.minutes < 60 && self.hours < 24
+    }
+
+    /* Counts one second the way the hardware does. Each counter only carries
+     * over when it goes past its normal maximum, so a value the game wrote
+     * out of range counts up to the bit width and wraps to 0 on its own.
+     */
+    fn tick_second(&mut self) {
+        if self.seconds != 59 {
+            self.seconds = (self.seconds + 1) & 0x3F;
+            return;
+        }
+        self.seconds = 0;
+        if self.minutes != 59 {
+            self.minutes = (self.minutes + 1) & 0x3F;
+            return;
+        }
+        self.minutes = 0;
+        if self.hours != 23 {
+            self.hours = (self.hours + 1) & 0x1F;
+            return;
+        }
+        self.hours = 0;
+        self.tick_days(1);
+    }
+
+    fn tick_days(&mut self, days: u64) {
+        let days = self.days as u64 + days;
+        if days > 0x1FF {
+            self.carry = true;
+        }
+        self.days = (days & 0x1FF) as u16;
+    }
+
+    fn advance(&mut self, mut seconds: u64) {
+        while seconds > 0 && !self.in_range() {
+            self.tick_second();
+            seconds -= 1;
+        }
+        // Once everything is in range the counters are plain digits.
+        let time = self.seconds as u64 + self.minutes as u64 * 60 + self.hours as u64 * 3600;
+        let time = time + seconds;
+        self.seconds = (time % 60) as u8;
+        self.minutes = (time / 60 % 60) as u8;
+        self.hours = (time / 3600 % 24) as u8;
+        self.tick_days(time / 86400);
+    }
+}
+
+/* The clock chip on MBC3 cartridges. It keeps counting while the Game Boy
+ * is off, so rather than ticking it along with the CPU we catch it up with
+ * the time source whenever it is looked at.
+ *
+ * 0x08  Seconds
+ * 0x09  Minutes
+ * 0x0A  Hours
+ * 0x0B  Low 8 bits of the day counter
+ * 0x0C  Bit 0 is bit 8 of the day counter, bit 6 halts the clock and bit 7
+ *       is the day counter carry
+ */
+#[derive(Debug)]
+pub struct Rtc {
+    clock: Box<dyn Clock>,
+    registers: RtcRegisters,
+    // What the game reads, a snapshot taken by the latch sequence.
+    latched: RtcRegisters,
+    // When `registers` was last brought up to date.
+    updated: u64,
+    latch_armed: bool,
+}
+
+impl Rtc {
+    pub fn new(clock: Box<dyn Clock>) -> Rtc {
+        let updated = clock.now();
+        Rtc {
+            clock,
+            registers: RtcRegisters::default(),
+            latched: RtcRegisters::default(),
+            updated,
+            latch_armed: false,
+        }
+    }
+
+    fn update(&mut self) {
+        let now = self.clock.now();
+        if !self.registers.halt {
+            self.registers.advance(now.saturating_sub(self.updated));
+        }
+        self.updated = now;
+    }
+
+    pub fn read(&self, register: u8) -> u8 {
+        self.latched.read(register)
+    }
+
+    pub fn write(&mut self, register: u8, val: u8) {
+        self.update();
+        self.registers.write(register, val);
+        self.latched.write(register, val);
+    }
+
+    /* Writing 0x00 and then 0x01 copies the running clock into the
+     * registers the game can read.
+     */
+    pub fn write_latch(&mut self, val: u8) {
+        if self.latch_armed && val == 0x01 {
+            self.update();
+            self.latched = self.registers;
+        }
+        self.latch_armed = val == 0x00;
+    }
+
+    /* The clock in BGB's save format: the five registers and then the five
+     * latched ones as 32-bit little-endian words, followed by the 64-bit
+     * time they were saved at.
+     */
+    pub fn save(&mut self) -> [u8; RTC_SAVE_SIZE] {
+        self.update();
+        let mut data = [0; RTC_SAVE_SIZE];
+        for (n, register) in (0x08..=0x0C).enumerate() {
+            data[n * 4] = self.registers.read(register);
+            data[20 + n * 4] = self.latched.read(register);
+        }
+        data[40..48].copy_from_slice(&self.updated.to_le_bytes());
+        data
+    }
+
+    /* Restores a clock saved by `save`, then lets it run for as long as it
+     * has been since then. Anything that isn't a clock save is ignored.
+     */
+    pub fn load(&mut self, data: &[u8]) {
+        let updated = match data.len() {
+            RTC_SAVE_SIZE => u64::from_le_bytes(data[40..48].try_into().unwrap()),
+            RTC_SAVE_SIZE_SHORT => u32::from_le_bytes(data[40..44].try_into().unwrap()) as u64,
+            _ => return,
+        };
+        for (n, register) in (0x08..=0x0C).enumerate() {
+            self.registers.write(register, data[n * 4]);
+            self.latched.write(register, data[20 + n * 4]);
+        }
+        self.updated = updated;
+        self.update();
+    }
+}
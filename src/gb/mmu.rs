@@ -1,52 +1,185 @@
-// This is a draft version of the MMU. Obviously not the real thing,
-// but we need to start somewhere.
+use crate::gb::boot::BootRom;
+use crate::gb::bus::Bus;
+use crate::gb::cartridge::{Cartridge, CartridgeError};
+use crate::gb::infrared::Infrared;
+use crate::gb::interrupts::{InterruptKind, Interrupts};
+use crate::gb::mbc::{MemoryBankController, RomOnly};
+use crate::gb::timer::Timer;
+
+/* Routes every address to the region that owns it:
+ *
+ * 0x0000-0x7FFF  ROM, switchable banks are up to the cartridge controller.
+ *                The boot ROM is laid over the start of it until 0xFF50 is
+ *                written to.
+ * 0x8000-0x9FFF  VRAM
+ * 0xA000-0xBFFF  External RAM on the cartridge, if any
+ * 0xC000-0xDFFF  WRAM
+ * 0xE000-0xFDFF  Echo RAM, mirrors 0xC000-0xDDFF
+ * 0xFE00-0xFE9F  OAM
+ * 0xFEA0-0xFEFF  Not usable
+ * 0xFF00-0xFF7F  I/O registers
+ * 0xFF80-0xFFFE  HRAM
+ * 0xFFFF         Interrupt enable register
+ */
 #[derive(Debug)]
 pub struct MemoryManagementUnit {
-    memory: [u8; 65536],
+    pub cartridge: Box<dyn MemoryBankController>,
+    boot_rom: Option<BootRom>,
+    vram: [u8; 0x2000],
+    wram: [u8; 0x2000],
+    oam: [u8; 0xA0],
+    // I/O registers that don't belong to an emulated device yet.
+    io: [u8; 0x80],
+    hram: [u8; 0x7F],
+    pub interrupts: Interrupts,
+    pub timer: Timer,
+    pub infrared: Infrared,
 }
 
-/* May not use at all, but these will be the regions of memory.
-pub enum Regions {
-    Bank0,
-    Bank1,
-    VRAM,
-    ExRam, // AKA External RAM from cartridge, switchable bank if any.
-    WRAM,
-    EchoRAM // Weird unused area of ram mirroring C000-DDFF
-    OAM,
-    IORegisters
-    HighRAM,
-    IERegister // Interrupt enable register
+impl Default for MemoryManagementUnit {
+    fn default() -> Self {
+        Self::new()
+    }
 }
-*/
 
 impl MemoryManagementUnit {
     pub fn new() -> MemoryManagementUnit {
         MemoryManagementUnit {
-            memory: [0; 65536],
+            cartridge: Box::new(RomOnly::default()),
+            boot_rom: None,
+            vram: [0; 0x2000],
+            wram: [0; 0x2000],
+            oam: [0; 0xA0],
+            io: [0; 0x80],
+            hram: [0; 0x7F],
+            interrupts: Interrupts::new(),
+            timer: Timer::new(),
+            infrared: Infrared::new(),
         }
     }
 
+    /* Inserts a cartridge without a controller holding the given ROM. */
     pub fn load_rom<'a>(&mut self, rom: impl Iterator<Item=&'a u8>) {
-        let mut pos: usize = 0;
-        for byte in rom {
-            self.memory[pos] = *byte;
-            pos += 1;
-        }
+        self.insert_cartridge(Box::new(RomOnly::new(rom.copied().collect())));
     }
 
-    /* Reads any address from the memory, regardless of where it belongs */
-    pub fn read_byte(&self, address: u16) -> u8 {
-        self.memory[address as usize]
+    pub fn insert_cartridge(&mut self, cartridge: Box<dyn MemoryBankController>) {
+        self.cartridge = cartridge;
     }
 
-    pub fn read_word(&self, address: u16) -> u16 {
-        (self.memory[address as usize] as u16) << 8 |
-            self.memory[(address + 1) as usize] as u16
+    /* Inserts a cartridge behind whatever controller its header names. */
+    pub fn load_cartridge(&mut self, cartridge: Cartridge) -> Result<(), CartridgeError> {
+        self.insert_cartridge(cartridge.into_controller()?);
+        Ok(())
+    }
+
+    /* Maps a boot ROM over the cartridge, to run at power on. */
+    pub fn load_boot_rom(&mut self, boot_rom: BootRom) {
+        self.boot_rom = Some(boot_rom);
+    }
+
+    pub fn boot_rom_mapped(&self) -> bool {
+        self.boot_rom.is_some()
+    }
+
+    pub fn read_byte(&self, address: u16) -> u8 {
+        match address {
+            0x0000..=0x7FFF => match &self.boot_rom {
+                Some(boot_rom) if boot_rom.maps(address) => boot_rom.read(address),
+                _ => self.cartridge.read_rom(address),
+            },
+            0x8000..=0x9FFF => self.vram[(address - 0x8000) as usize],
+            0xA000..=0xBFFF => self.cartridge.read_ram(address),
+            0xC000..=0xDFFF => self.wram[(address - 0xC000) as usize],
+            0xE000..=0xFDFF => self.wram[(address - 0xE000) as usize],
+            0xFE00..=0xFE9F => self.oam[(address - 0xFE00) as usize],
+            // Reads as 0x00 on DMG. Real hardware returns 0xFF while the PPU
+            // has OAM locked, which will need handling once there is a PPU.
+            0xFEA0..=0xFEFF => 0x00,
+            0xFF00..=0xFF7F => self.read_io(address),
+            0xFF80..=0xFFFE => self.hram[(address - 0xFF80) as usize],
+            0xFFFF => self.interrupts.read_enable(),
+        }
     }
 
     pub fn set_byte(&mut self, address: u16, val: u8) {
-        self.memory[address as usize] = val;
+        match address {
+            0x0000..=0x7FFF => self.cartridge.write_rom(address, val),
+            0x8000..=0x9FFF => self.vram[(address - 0x8000) as usize] = val,
+            0xA000..=0xBFFF => self.cartridge.write_ram(address, val),
+            0xC000..=0xDFFF => self.wram[(address - 0xC000) as usize] = val,
+            0xE000..=0xFDFF => self.wram[(address - 0xE000) as usize] = val,
+            0xFE00..=0xFE9F => self.oam[(address - 0xFE00) as usize] = val,
+            0xFEA0..=0xFEFF => {}
+            0xFF00..=0xFF7F => self.write_io(address, val),
+            0xFF80..=0xFFFE => self.hram[(address - 0xFF80) as usize] = val,
+            0xFFFF => self.interrupts.write_enable(val),
+        }
+    }
+
+    /* Hands I/O register accesses to the device that owns them. Registers
+     * of devices that aren't emulated yet are plain bytes of storage.
+     */
+    fn read_io(&self, address: u16) -> u8 {
+        match address {
+            0xFF04 => self.timer.read_div(),
+            0xFF0F => self.interrupts.read_flag(),
+            0xFF50 => 0xFF,
+            0xFF56 => self.infrared.read_rp(),
+            _ => self.io[(address - 0xFF00) as usize],
+        }
+    }
+
+    fn write_io(&mut self, address: u16, val: u8) {
+        match address {
+            0xFF04 => self.timer.write_div(),
+            0xFF0F => self.interrupts.write_flag(val),
+            // Only bit 0 counts, and once the boot ROM is gone it stays gone
+            // until the next power cycle.
+            0xFF50 => {
+                if val & 0x01 != 0 {
+                    self.boot_rom = None;
+                }
+            }
+            0xFF56 => self.infrared.write_rp(val),
+            _ => self.io[(address - 0xFF00) as usize] = val,
+        }
+    }
+
+    /* Advances everything clocked alongside the CPU by one M-cycle. The CPU
+     * calls this once for every memory access and internal cycle it makes,
+     * so other hardware sees changes in the middle of an instruction.
+     */
+    pub fn tick(&mut self) {
+        self.timer.tick();
+    }
+
+    /* Entry point for the timer, PPU, serial port and joypad to raise an
+     * interrupt by setting its bit in IF.
+     */
+    pub fn request_interrupt(&mut self, kind: InterruptKind) {
+        self.interrupts.request(kind);
     }
 }
 
+impl Bus for MemoryManagementUnit {
+    fn read(&mut self, address: u16) -> u8 {
+        self.read_byte(address)
+    }
+
+    fn write(&mut self, address: u16, val: u8) {
+        self.set_byte(address, val);
+    }
+
+    fn tick(&mut self) {
+        MemoryManagementUnit::tick(self);
+    }
+
+    fn pending_interrupts(&mut self) -> u8 {
+        self.interrupts.pending()
+    }
+
+    fn acknowledge_interrupt(&mut self, kind: InterruptKind) {
+        self.interrupts.acknowledge(kind);
+    }
+}
@@ -1,4 +1,13 @@
+pub mod boot;
+pub mod bus;
+pub mod cartridge;
 pub mod cpu;
+pub mod infrared;
 pub mod instructions;
+pub mod interrupts;
+pub mod mbc;
 pub mod mmu;
+pub mod model;
+pub mod opcodes;
 pub mod registers;
+pub mod timer;
@@ -0,0 +1,143 @@
+use crate::gb::registers::Registers;
+
+/* The Game Boy models whose boot ROMs we know the end state of. Games and
+ * test ROMs tell them apart by the registers the boot ROM leaves behind.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Model {
+    Dmg0,
+    Dmg,
+    Mgb,
+    Sgb,
+    Sgb2,
+    Cgb,
+    Agb,
+}
+
+// I/O registers every model leaves the same way. Values are from Pan Docs.
+const COMMON_IO: [(u16, u8); 35] = [
+    (0xFF00, 0xCF), // P1
+    (0xFF01, 0x00), // SB
+    (0xFF05, 0x00), // TIMA
+    (0xFF06, 0x00), // TMA
+    (0xFF07, 0xF8), // TAC
+    (0xFF0F, 0xE1), // IF
+    (0xFF10, 0x80), // NR10
+    (0xFF11, 0xBF), // NR11
+    (0xFF12, 0xF3), // NR12
+    (0xFF13, 0xFF), // NR13
+    (0xFF14, 0xBF), // NR14
+    (0xFF16, 0x3F), // NR21
+    (0xFF17, 0x00), // NR22
+    (0xFF18, 0xFF), // NR23
+    (0xFF19, 0xBF), // NR24
+    (0xFF1A, 0x7F), // NR30
+    (0xFF1B, 0xFF), // NR31
+    (0xFF1C, 0x9F), // NR32
+    (0xFF1D, 0xFF), // NR33
+    (0xFF1E, 0xBF), // NR34
+    (0xFF20, 0xFF), // NR41
+    (0xFF21, 0x00), // NR42
+    (0xFF22, 0x00), // NR43
+    (0xFF23, 0xBF), // NR44
+    (0xFF24, 0x77), // NR50
+    (0xFF25, 0xF3), // NR51
+    (0xFF40, 0x91), // LCDC
+    (0xFF42, 0x00), // SCY
+    (0xFF43, 0x00), // SCX
+    (0xFF45, 0x00), // LYC
+    (0xFF47, 0xFC), // BGP
+    (0xFF48, 0xFF), // OBP0
+    (0xFF49, 0xFF), // OBP1
+    (0xFF4A, 0x00), // WY
+    (0xFF4B, 0x00), // WX
+];
+
+// Registers only the CGB and AGB have.
+const CGB_IO: [(u16, u8); 10] = [
+    (0xFF4D, 0x7E), // KEY1
+    (0xFF4F, 0xFE), // VBK
+    (0xFF51, 0xFF), // HDMA1
+    (0xFF52, 0xFF), // HDMA2
+    (0xFF53, 0xFF), // HDMA3
+    (0xFF54, 0xFF), // HDMA4
+    (0xFF55, 0xFF), // HDMA5
+    (0xFF56, 0x3E), // RP
+    (0xFF6C, 0xFE), // OPRI
+    (0xFF70, 0xF8), // SVBK
+];
+
+impl Model {
+    pub fn is_cgb(self) -> bool {
+        matches!(self, Model::Cgb | Model::Agb)
+    }
+
+    pub fn is_sgb(self) -> bool {
+        matches!(self, Model::Sgb | Model::Sgb2)
+    }
+
+    /* CPU registers right as the boot ROM jumps to 0x0100. The DMG and MGB
+     * boot ROMs leave H and C set unless the header checksum at 0x014D is 0.
+     * Color models are given the values they leave for a CGB cartridge.
+     */
+    pub fn boot_registers(self, header_checksum: u8) -> Registers {
+        let hc = if header_checksum == 0 { 0x00 } else { 0x30 };
+        let (af, bc, de, hl) = match self {
+            Model::Dmg0 => (0x0100, 0xFF13, 0x00C1, 0x8403),
+            Model::Dmg => (0x0180 | hc, 0x0013, 0x00D8, 0x014D),
+            Model::Mgb => (0xFF80 | hc, 0x0013, 0x00D8, 0x014D),
+            Model::Sgb => (0x0100, 0x0014, 0x0000, 0xC060),
+            Model::Sgb2 => (0xFF00, 0x0014, 0x0000, 0xC060),
+            Model::Cgb => (0x1180, 0x0000, 0xFF56, 0x000D),
+            Model::Agb => (0x1100, 0x0100, 0xFF56, 0x000D),
+        };
+        let mut registers = Registers::new();
+        registers.set_af(af);
+        registers.set_bc(bc);
+        registers.set_de(de);
+        registers.set_hl(hl);
+        registers.sp = 0xFFFE;
+        registers.pc = 0x0100;
+        registers
+    }
+
+    /* I/O register contents the boot ROM leaves behind, as (address, value)
+     * pairs. This includes the write to 0xFF50 that unmaps the boot ROM.
+     */
+    pub fn boot_io(self) -> Vec<(u16, u8)> {
+        let mut io = COMMON_IO.to_vec();
+        let sc = if self.is_cgb() { 0x7F } else { 0x7E };
+        let nr52 = if self.is_sgb() { 0xF0 } else { 0xF1 };
+        let dma = if self.is_cgb() { 0x00 } else { 0xFF };
+        let (stat, ly) = match self {
+            Model::Dmg0 => (0x81, 0x91),
+            _ => (0x85, 0x00),
+        };
+        io.extend([
+            (0xFF02, sc),
+            (0xFF26, nr52),
+            (0xFF41, stat),
+            (0xFF44, ly),
+            (0xFF46, dma),
+        ]);
+        if self.is_cgb() {
+            io.extend(CGB_IO);
+        }
+        io.push((0xFF50, 0x01));
+        io
+    }
+
+    /* The internal DIV counter at 0x0100. DIV itself is the upper byte. The
+     * DMG0, DMG and MGB boot ROMs always take the same time to run. The SGB
+     * boot time depends on the SGB BIOS and the CGB one on the cartridge
+     * header, so those are typical values rather than exact ones.
+     */
+    pub fn boot_div(self) -> u16 {
+        match self {
+            Model::Dmg0 => 0x182C,
+            Model::Dmg | Model::Mgb => 0xABCC,
+            Model::Sgb | Model::Sgb2 => 0xD85C,
+            Model::Cgb | Model::Agb => 0x267C,
+        }
+    }
+}
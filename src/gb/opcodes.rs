@@ -0,0 +1,645 @@
+/*
+ * Metadata for all 256 base and 256 CB-prefixed opcodes. The rows below are
+ * laid out to be checked line by line against the published opcode tables
+ * (Pan Docs, gbdev.io/gb-opcodes), while the decoded Instruction for each
+ * entry is filled in at compile time by Instruction::decode.
+ *
+ * Cycles are in M-cycles. For conditional JR, JP, CALL and RET, `cycles` is
+ * the cost when the branch is not taken and `branch_cycles` when it is.
+ */
+
+use crate::gb::instructions::{Instruction, InstructionError};
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlagEffect {
+    Unaffected,
+    Reset,
+    Set,
+    // Set or reset depending on the result.
+    Affected,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlagEffects {
+    pub z: FlagEffect,
+    pub n: FlagEffect,
+    pub h: FlagEffect,
+    pub c: FlagEffect,
+}
+
+impl FlagEffects {
+    /* Parses the usual table notation, one character per flag in ZNHC order:
+     * '-' unaffected, '0' reset, '1' set and the flag's letter for affected.
+     */
+    const fn parse(notation: &str) -> FlagEffects {
+        let bytes = notation.as_bytes();
+        FlagEffects {
+            z: FlagEffects::parse_one(bytes[0]),
+            n: FlagEffects::parse_one(bytes[1]),
+            h: FlagEffects::parse_one(bytes[2]),
+            c: FlagEffects::parse_one(bytes[3]),
+        }
+    }
+
+    const fn parse_one(byte: u8) -> FlagEffect {
+        match byte {
+            b'-' => FlagEffect::Unaffected,
+            b'0' => FlagEffect::Reset,
+            b'1' => FlagEffect::Set,
+            _ => FlagEffect::Affected,
+        }
+    }
+}
+
+impl fmt::Display for FlagEffects {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let flags = [(self.z, 'Z'), (self.n, 'N'), (self.h, 'H'), (self.c, 'C')];
+        for (effect, name) in flags {
+            let symbol = match effect {
+                FlagEffect::Unaffected => '-',
+                FlagEffect::Reset => '0',
+                FlagEffect::Set => '1',
+                FlagEffect::Affected => name,
+            };
+            write!(f, "{symbol}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Opcode {
+    // Err(InstructionError::Invalid) for the eleven unused opcodes, and
+    // Err(InstructionError::NotFound) for 0xCB, which is only a prefix.
+    pub instruction: Result<Instruction, InstructionError>,
+    pub mnemonic: &'static str,
+    // Bytes including the 0xCB prefix and any immediate operand.
+    pub length: u8,
+    pub cycles: u8,
+    pub branch_cycles: Option<u8>,
+    pub flags: FlagEffects,
+}
+
+impl Opcode {
+    pub fn lookup(byte: u8, prefixed: bool) -> &'static Opcode {
+        if prefixed {
+            &CB_OPCODES[byte as usize]
+        } else {
+            &OPCODES[byte as usize]
+        }
+    }
+}
+
+pub static OPCODES: [Opcode; 256] = build_table(&BASE_ROWS, false);
+pub static CB_OPCODES: [Opcode; 256] = build_table(&CB_ROWS, true);
+
+// (mnemonic, length, cycles, branch cycles, flags)
+type Row = (&'static str, u8, u8, Option<u8>, &'static str);
+
+const fn build_table(rows: &[Row; 256], prefixed: bool) -> [Opcode; 256] {
+    let mut table = [Opcode {
+        instruction: Err(InstructionError::NotFound),
+        mnemonic: "",
+        length: 0,
+        cycles: 0,
+        branch_cycles: None,
+        flags: FlagEffects::parse("----"),
+    }; 256];
+
+    let mut byte: usize = 0;
+    while byte < 256 {
+        let (mnemonic, length, cycles, branch_cycles, flags) = rows[byte];
+        table[byte] = Opcode {
+            instruction: Instruction::decode(byte as u8, prefixed),
+            mnemonic,
+            length,
+            cycles,
+            branch_cycles,
+            flags: FlagEffects::parse(flags),
+        };
+        byte += 1;
+    }
+    table
+}
+
+#[rustfmt::skip]
+const BASE_ROWS: [Row; 256] = [
+    /* 00 */ ("NOP", 1, 1, None, "----"),
+    /* 01 */ ("LD BC, n16", 3, 3, None, "----"),
+    /* 02 */ ("LD [BC], A", 1, 2, None, "----"),
+    /* 03 */ ("INC BC", 1, 2, None, "----"),
+    /* 04 */ ("INC B", 1, 1, None, "Z0H-"),
+    /* 05 */ ("DEC B", 1, 1, None, "Z1H-"),
+    /* 06 */ ("LD B, n8", 2, 2, None, "----"),
+    /* 07 */ ("RLCA", 1, 1, None, "000C"),
+    /* 08 */ ("LD [a16], SP", 3, 5, None, "----"),
+    /* 09 */ ("ADD HL, BC", 1, 2, None, "-0HC"),
+    /* 0A */ ("LD A, [BC]", 1, 2, None, "----"),
+    /* 0B */ ("DEC BC", 1, 2, None, "----"),
+    /* 0C */ ("INC C", 1, 1, None, "Z0H-"),
+    /* 0D */ ("DEC C", 1, 1, None, "Z1H-"),
+    /* 0E */ ("LD C, n8", 2, 2, None, "----"),
+    /* 0F */ ("RRCA", 1, 1, None, "000C"),
+    /* 10 */ ("STOP", 2, 1, None, "----"),
+    /* 11 */ ("LD DE, n16", 3, 3, None, "----"),
+    /* 12 */ ("LD [DE], A", 1, 2, None, "----"),
+    /* 13 */ ("INC DE", 1, 2, None, "----"),
+    /* 14 */ ("INC D", 1, 1, None, "Z0H-"),
+    /* 15 */ ("DEC D", 1, 1, None, "Z1H-"),
+    /* 16 */ ("LD D, n8", 2, 2, None, "----"),
+    /* 17 */ ("RLA", 1, 1, None, "000C"),
+    /* 18 */ ("JR e8", 2, 3, None, "----"),
+    /* 19 */ ("ADD HL, DE", 1, 2, None, "-0HC"),
+    /* 1A */ ("LD A, [DE]", 1, 2, None, "----"),
+    /* 1B */ ("DEC DE", 1, 2, None, "----"),
+    /* 1C */ ("INC E", 1, 1, None, "Z0H-"),
+    /* 1D */ ("DEC E", 1, 1, None, "Z1H-"),
+    /* 1E */ ("LD E, n8", 2, 2, None, "----"),
+    /* 1F */ ("RRA", 1, 1, None, "000C"),
+    /* 20 */ ("JR NZ, e8", 2, 2, Some(3), "----"),
+    /* 21 */ ("LD HL, n16", 3, 3, None, "----"),
+    /* 22 */ ("LD [HL+], A", 1, 2, None, "----"),
+    /* 23 */ ("INC HL", 1, 2, None, "----"),
+    /* 24 */ ("INC H", 1, 1, None, "Z0H-"),
+    /* 25 */ ("DEC H", 1, 1, None, "Z1H-"),
+    /* 26 */ ("LD H, n8", 2, 2, None, "----"),
+    /* 27 */ ("DAA", 1, 1, None, "Z-0C"),
+    /* 28 */ ("JR Z, e8", 2, 2, Some(3), "----"),
+    /* 29 */ ("ADD HL, HL", 1, 2, None, "-0HC"),
+    /* 2A */ ("LD A, [HL+]", 1, 2, None, "----"),
+    /* 2B */ ("DEC HL", 1, 2, None, "----"),
+    /* 2C */ ("INC L", 1, 1, None, "Z0H-"),
+    /* 2D */ ("DEC L", 1, 1, None, "Z1H-"),
+    /* 2E */ ("LD L, n8", 2, 2, None, "----"),
+    /* 2F */ ("CPL", 1, 1, None, "-11-"),
+    /* 30 */ ("JR NC, e8", 2, 2, Some(3), "----"),
+    /* 31 */ ("LD SP, n16", 3, 3, None, "----"),
+    /* 32 */ ("LD [HL-], A", 1, 2, None, "----"),
+    /* 33 */ ("INC SP", 1, 2, None, "----"),
+    /* 34 */ ("INC [HL]", 1, 3, None, "Z0H-"),
+    /* 35 */ ("DEC [HL]", 1, 3, None, "Z1H-"),
+    /* 36 */ ("LD [HL], n8", 2, 3, None, "----"),
+    /* 37 */ ("SCF", 1, 1, None, "-001"),
+    /* 38 */ ("JR C, e8", 2, 2, Some(3), "----"),
+    /* 39 */ ("ADD HL, SP", 1, 2, None, "-0HC"),
+    /* 3A */ ("LD A, [HL-]", 1, 2, None, "----"),
+    /* 3B */ ("DEC SP", 1, 2, None, "----"),
+    /* 3C */ ("INC A", 1, 1, None, "Z0H-"),
+    /* 3D */ ("DEC A", 1, 1, None, "Z1H-"),
+    /* 3E */ ("LD A, n8", 2, 2, None, "----"),
+    /* 3F */ ("CCF", 1, 1, None, "-00C"),
+    /* 40 */ ("LD B, B", 1, 1, None, "----"),
+    /* 41 */ ("LD B, C", 1, 1, None, "----"),
+    /* 42 */ ("LD B, D", 1, 1, None, "----"),
+    /* 43 */ ("LD B, E", 1, 1, None, "----"),
+    /* 44 */ ("LD B, H", 1, 1, None, "----"),
+    /* 45 */ ("LD B, L", 1, 1, None, "----"),
+    /* 46 */ ("LD B, [HL]", 1, 2, None, "----"),
+    /* 47 */ ("LD B, A", 1, 1, None, "----"),
+    /* 48 */ ("LD C, B", 1, 1, None, "----"),
+    /* 49 */ ("LD C, C", 1, 1, None, "----"),
+    /* 4A */ ("LD C, D", 1, 1, None, "----"),
+    /* 4B */ ("LD C, E", 1, 1, None, "----"),
+    /* 4C */ ("LD C, H", 1, 1, None, "----"),
+    /* 4D */ ("LD C, L", 1, 1, None, "----"),
+    /* 4E */ ("LD C, [HL]", 1, 2, None, "----"),
+    /* 4F */ ("LD C, A", 1, 1, None, "----"),
+    /* 50 */ ("LD D, B", 1, 1, None, "----"),
+    /* 51 */ ("LD D, C", 1, 1, None, "----"),
+    /* 52 */ ("LD D, D", 1, 1, None, "----"),
+    /* 53 */ ("LD D, E", 1, 1, None, "----"),
+    /* 54 */ ("LD D, H", 1, 1, None, "----"),
+    /* 55 */ ("LD D, L", 1, 1, None, "----"),
+    /* 56 */ ("LD D, [HL]", 1, 2, None, "----"),
+    /* 57 */ ("LD D, A", 1, 1, None, "----"),
+    /* 58 */ ("LD E, B", 1, 1, None, "----"),
+    /* 59 */ ("LD E, C", 1, 1, None, "----"),
+    /* 5A */ ("LD E, D", 1, 1, None, "----"),
+    /* 5B */ ("LD E, E", 1, 1, None, "----"),
+    /* 5C */ ("LD E, H", 1, 1, None, "----"),
+    /* 5D */ ("LD E, L", 1, 1, None, "----"),
+    /* 5E */ ("LD E, [HL]", 1, 2, None, "----"),
+    /* 5F */ ("LD E, A", 1, 1, None, "----"),
+    /* 60 */ ("LD H, B", 1, 1, None, "----"),
+    /* 61 */ ("LD H, C", 1, 1, None, "----"),
+    /* 62 */ ("LD H, D", 1, 1, None, "----"),
+    /* 63 */ ("LD H, E", 1, 1, None, "----"),
+    /* 64 */ ("LD H, H", 1, 1, None, "----"),
+    /* 65 */ ("LD H, L", 1, 1, None, "----"),
+    /* 66 */ ("LD H, [HL]", 1, 2, None, "----"),
+    /* 67 */ ("LD H, A", 1, 1, None, "----"),
+    /* 68 */ ("LD L, B", 1, 1, None, "----"),
+    /* 69 */ ("LD L, C", 1, 1, None, "----"),
+    /* 6A */ ("LD L, D", 1, 1, None, "----"),
+    /* 6B */ ("LD L, E", 1, 1, None, "----"),
+    /* 6C */ ("LD L, H", 1, 1, None, "----"),
+    /* 6D */ ("LD L, L", 1, 1, None, "----"),
+    /* 6E */ ("LD L, [HL]", 1, 2, None, "----"),
+    /* 6F */ ("LD L, A", 1, 1, None, "----"),
+    /* 70 */ ("LD [HL], B", 1, 2, None, "----"),
+    /* 71 */ ("LD [HL], C", 1, 2, None, "----"),
+    /* 72 */ ("LD [HL], D", 1, 2, None, "----"),
+    /* 73 */ ("LD [HL], E", 1, 2, None, "----"),
+    /* 74 */ ("LD [HL], H", 1, 2, None, "----"),
+    /* 75 */ ("LD [HL], L", 1, 2, None, "----"),
+    /* 76 */ ("HALT", 1, 1, None, "----"),
+    /* 77 */ ("LD [HL], A", 1, 2, None, "----"),
+    /* 78 */ ("LD A, B", 1, 1, None, "----"),
+    /* 79 */ ("LD A, C", 1, 1, None, "----"),
+    /* 7A */ ("LD A, D", 1, 1, None, "----"),
+    /* 7B */ ("LD A, E", 1, 1, None, "----"),
+    /* 7C */ ("LD A, H", 1, 1, None, "----"),
+    /* 7D */ ("LD A, L", 1, 1, None, "----"),
+    /* 7E */ ("LD A, [HL]", 1, 2, None, "----"),
+    /* 7F */ ("LD A, A", 1, 1, None, "----"),
+    /* 80 */ ("ADD A, B", 1, 1, None, "Z0HC"),
+    /* 81 */ ("ADD A, C", 1, 1, None, "Z0HC"),
+    /* 82 */ ("ADD A, D", 1, 1, None, "Z0HC"),
+    /* 83 */ ("ADD A, E", 1, 1, None, "Z0HC"),
+    /* 84 */ ("ADD A, H", 1, 1, None, "Z0HC"),
+    /* 85 */ ("ADD A, L", 1, 1, None, "Z0HC"),
+    /* 86 */ ("ADD A, [HL]", 1, 2, None, "Z0HC"),
+    /* 87 */ ("ADD A, A", 1, 1, None, "Z0HC"),
+    /* 88 */ ("ADC A, B", 1, 1, None, "Z0HC"),
+    /* 89 */ ("ADC A, C", 1, 1, None, "Z0HC"),
+    /* 8A */ ("ADC A, D", 1, 1, None, "Z0HC"),
+    /* 8B */ ("ADC A, E", 1, 1, None, "Z0HC"),
+    /* 8C */ ("ADC A, H", 1, 1, None, "Z0HC"),
+    /* 8D */ ("ADC A, L", 1, 1, None, "Z0HC"),
+    /* 8E */ ("ADC A, [HL]", 1, 2, None, "Z0HC"),
+    /* 8F */ ("ADC A, A", 1, 1, None, "Z0HC"),
+    /* 90 */ ("SUB A, B", 1, 1, None, "Z1HC"),
+    /* 91 */ ("SUB A, C", 1, 1, None, "Z1HC"),
+    /* 92 */ ("SUB A, D", 1, 1, None, "Z1HC"),
+    /* 93 */ ("SUB A, E", 1, 1, None, "Z1HC"),
+    /* 94 */ ("SUB A, H", 1, 1, None, "Z1HC"),
+    /* 95 */ ("SUB A, L", 1, 1, None, "Z1HC"),
+    /* 96 */ ("SUB A, [HL]", 1, 2, None, "Z1HC"),
+    /* 97 */ ("SUB A, A", 1, 1, None, "Z1HC"),
+    /* 98 */ ("SBC A, B", 1, 1, None, "Z1HC"),
+    /* 99 */ ("SBC A, C", 1, 1, None, "Z1HC"),
+    /* 9A */ ("SBC A, D", 1, 1, None, "Z1HC"),
+    /* 9B */ ("SBC A, E", 1, 1, None, "Z1HC"),
+    /* 9C */ ("SBC A, H", 1, 1, None, "Z1HC"),
+    /* 9D */ ("SBC A, L", 1, 1, None, "Z1HC"),
+    /* 9E */ ("SBC A, [HL]", 1, 2, None, "Z1HC"),
+    /* 9F */ ("SBC A, A", 1, 1, None, "Z1HC"),
+    /* A0 */ ("AND A, B", 1, 1, None, "Z010"),
+    /* A1 */ ("AND A, C", 1, 1, None, "Z010"),
+    /* A2 */ ("AND A, D", 1, 1, None, "Z010"),
+    /* A3 */ ("AND A, E", 1, 1, None, "Z010"),
+    /* A4 */ ("AND A, H", 1, 1, None, "Z010"),
+    /* A5 */ ("AND A, L", 1, 1, None, "Z010"),
+    /* A6 */ ("AND A, [HL]", 1, 2, None, "Z010"),
+    /* A7 */ ("AND A, A", 1, 1, None, "Z010"),
+    /* A8 */ ("XOR A, B", 1, 1, None, "Z000"),
+    /* A9 */ ("XOR A, C", 1, 1, None, "Z000"),
+    /* AA */ ("XOR A, D", 1, 1, None, "Z000"),
+    /* AB */ ("XOR A, E", 1, 1, None, "Z000"),
+    /* AC */ ("XOR A, H", 1, 1, None, "Z000"),
+    /* AD */ ("XOR A, L", 1, 1, None, "Z000"),
+    /* AE */ ("XOR A, [HL]", 1, 2, None, "Z000"),
+    /* AF */ ("XOR A, A", 1, 1, None, "Z000"),
+    /* B0 */ ("OR A, B", 1, 1, None, "Z000"),
+    /* B1 */ ("OR A, C", 1, 1, None, "Z000"),
+    /* B2 */ ("OR A, D", 1, 1, None, "Z000"),
+    /* B3 */ ("OR A, E", 1, 1, None, "Z000"),
+    /* B4 */ ("OR A, H", 1, 1, None, "Z000"),
+    /* B5 */ ("OR A, L", 1, 1, None, "Z000"),
+    /* B6 */ ("OR A, [HL]", 1, 2, None, "Z000"),
+    /* B7 */ ("OR A, A", 1, 1, None, "Z000"),
+    /* B8 */ ("CP A, B", 1, 1, None, "Z1HC"),
+    /* B9 */ ("CP A, C", 1, 1, None, "Z1HC"),
+    /* BA */ ("CP A, D", 1, 1, None, "Z1HC"),
+    /* BB */ ("CP A, E", 1, 1, None, "Z1HC"),
+    /* BC */ ("CP A, H", 1, 1, None, "Z1HC"),
+    /* BD */ ("CP A, L", 1, 1, None, "Z1HC"),
+    /* BE */ ("CP A, [HL]", 1, 2, None, "Z1HC"),
+    /* BF */ ("CP A, A", 1, 1, None, "Z1HC"),
+    /* C0 */ ("RET NZ", 1, 2, Some(5), "----"),
+    /* C1 */ ("POP BC", 1, 3, None, "----"),
+    /* C2 */ ("JP NZ, a16", 3, 3, Some(4), "----"),
+    /* C3 */ ("JP a16", 3, 4, None, "----"),
+    /* C4 */ ("CALL NZ, a16", 3, 3, Some(6), "----"),
+    /* C5 */ ("PUSH BC", 1, 4, None, "----"),
+    /* C6 */ ("ADD A, n8", 2, 2, None, "Z0HC"),
+    /* C7 */ ("RST $00", 1, 4, None, "----"),
+    /* C8 */ ("RET Z", 1, 2, Some(5), "----"),
+    /* C9 */ ("RET", 1, 4, None, "----"),
+    /* CA */ ("JP Z, a16", 3, 3, Some(4), "----"),
+    /* CB */ ("PREFIX", 1, 1, None, "----"),
+    /* CC */ ("CALL Z, a16", 3, 3, Some(6), "----"),
+    /* CD */ ("CALL a16", 3, 6, None, "----"),
+    /* CE */ ("ADC A, n8", 2, 2, None, "Z0HC"),
+    /* CF */ ("RST $08", 1, 4, None, "----"),
+    /* D0 */ ("RET NC", 1, 2, Some(5), "----"),
+    /* D1 */ ("POP DE", 1, 3, None, "----"),
+    /* D2 */ ("JP NC, a16", 3, 3, Some(4), "----"),
+    /* D3 */ ("ILLEGAL_D3", 1, 1, None, "----"),
+    /* D4 */ ("CALL NC, a16", 3, 3, Some(6), "----"),
+    /* D5 */ ("PUSH DE", 1, 4, None, "----"),
+    /* D6 */ ("SUB A, n8", 2, 2, None, "Z1HC"),
+    /* D7 */ ("RST $10", 1, 4, None, "----"),
+    /* D8 */ ("RET C", 1, 2, Some(5), "----"),
+    /* D9 */ ("RETI", 1, 4, None, "----"),
+    /* DA */ ("JP C, a16", 3, 3, Some(4), "----"),
+    /* DB */ ("ILLEGAL_DB", 1, 1, None, "----"),
+    /* DC */ ("CALL C, a16", 3, 3, Some(6), "----"),
+    /* DD */ ("ILLEGAL_DD", 1, 1, None, "----"),
+    /* DE */ ("SBC A, n8", 2, 2, None, "Z1HC"),
+    /* DF */ ("RST $18", 1, 4, None, "----"),
+    /* E0 */ ("LDH [a8], A", 2, 3, None, "----"),
+    /* E1 */ ("POP HL", 1, 3, None, "----"),
+    /* E2 */ ("LDH [C], A", 1, 2, None, "----"),
+    /* E3 */ ("ILLEGAL_E3", 1, 1, None, "----"),
+    /* E4 */ ("ILLEGAL_E4", 1, 1, None, "----"),
+    /* E5 */ ("PUSH HL", 1, 4, None, "----"),
+    /* E6 */ ("AND A, n8", 2, 2, None, "Z010"),
+    /* E7 */ ("RST $20", 1, 4, None, "----"),
+    /* E8 */ ("ADD SP, e8", 2, 4, None, "00HC"),
+    /* E9 */ ("JP HL", 1, 1, None, "----"),
+    /* EA */ ("LD [a16], A", 3, 4, None, "----"),
+    /* EB */ ("ILLEGAL_EB", 1, 1, None, "----"),
+    /* EC */ ("ILLEGAL_EC", 1, 1, None, "----"),
+    /* ED */ ("ILLEGAL_ED", 1, 1, None, "----"),
+    /* EE */ ("XOR A, n8", 2, 2, None, "Z000"),
+    /* EF */ ("RST $28", 1, 4, None, "----"),
+    /* F0 */ ("LDH A, [a8]", 2, 3, None, "----"),
+    /* F1 */ ("POP AF", 1, 3, None, "ZNHC"),
+    /* F2 */ ("LDH A, [C]", 1, 2, None, "----"),
+    /* F3 */ ("DI", 1, 1, None, "----"),
+    /* F4 */ ("ILLEGAL_F4", 1, 1, None, "----"),
+    /* F5 */ ("PUSH AF", 1, 4, None, "----"),
+    /* F6 */ ("OR A, n8", 2, 2, None, "Z000"),
+    /* F7 */ ("RST $30", 1, 4, None, "----"),
+    /* F8 */ ("LD HL, SP+e8", 2, 3, None, "00HC"),
+    /* F9 */ ("LD SP, HL", 1, 2, None, "----"),
+    /* FA */ ("LD A, [a16]", 3, 4, None, "----"),
+    /* FB */ ("EI", 1, 1, None, "----"),
+    /* FC */ ("ILLEGAL_FC", 1, 1, None, "----"),
+    /* FD */ ("ILLEGAL_FD", 1, 1, None, "----"),
+    /* FE */ ("CP A, n8", 2, 2, None, "Z1HC"),
+    /* FF */ ("RST $38", 1, 4, None, "----"),
+];
+
+#[rustfmt::skip]
+const CB_ROWS: [Row; 256] = [
+    /* 00 */ ("RLC B", 2, 2, None, "Z00C"),
+    /* 01 */ ("RLC C", 2, 2, None, "Z00C"),
+    /* 02 */ ("RLC D", 2, 2, None, "Z00C"),
+    /* 03 */ ("RLC E", 2, 2, None, "Z00C"),
+    /* 04 */ ("RLC H", 2, 2, None, "Z00C"),
+    /* 05 */ ("RLC L", 2, 2, None, "Z00C"),
+    /* 06 */ ("RLC [HL]", 2, 4, None, "Z00C"),
+    /* 07 */ ("RLC A", 2, 2, None, "Z00C"),
+    /* 08 */ ("RRC B", 2, 2, None, "Z00C"),
+    /* 09 */ ("RRC C", 2, 2, None, "Z00C"),
+    /* 0A */ ("RRC D", 2, 2, None, "Z00C"),
+    /* 0B */ ("RRC E", 2, 2, None, "Z00C"),
+    /* 0C */ ("RRC H", 2, 2, None, "Z00C"),
+    /* 0D */ ("RRC L", 2, 2, None, "Z00C"),
+    /* 0E */ ("RRC [HL]", 2, 4, None, "Z00C"),
+    /* 0F */ ("RRC A", 2, 2, None, "Z00C"),
+    /* 10 */ ("RL B", 2, 2, None, "Z00C"),
+    /* 11 */ ("RL C", 2, 2, None, "Z00C"),
+    /* 12 */ ("RL D", 2, 2, None, "Z00C"),
+    /* 13 */ ("RL E", 2, 2, None, "Z00C"),
+    /* 14 */ ("RL H", 2, 2, None, "Z00C"),
+    /* 15 */ ("RL L", 2, 2, None, "Z00C"),
+    /* 16 */ ("RL [HL]", 2, 4, None, "Z00C"),
+    /* 17 */ ("RL A", 2, 2, None, "Z00C"),
+    /* 18 */ ("RR B", 2, 2, None, "Z00C"),
+    /* 19 */ ("RR C", 2, 2, None, "Z00C"),
+    /* 1A */ ("RR D", 2, 2, None, "Z00C"),
+    /* 1B */ ("RR E", 2, 2, None, "Z00C"),
+    /* 1C */ ("RR H", 2, 2, None, "Z00C"),
+    /* 1D */ ("RR L", 2, 2, None, "Z00C"),
+    /* 1E */ ("RR [HL]", 2, 4, None, "Z00C"),
+    /* 1F */ ("RR A", 2, 2, None, "Z00C"),
+    /* 20 */ ("SLA B", 2, 2, None, "Z00C"),
+    /* 21 */ ("SLA C", 2, 2, None, "Z00C"),
+    /* 22 */ ("SLA D", 2, 2, None, "Z00C"),
+    /* 23 */ ("SLA E", 2, 2, None, "Z00C"),
+    /* 24 */ ("SLA H", 2, 2, None, "Z00C"),
+    /* 25 */ ("SLA L", 2, 2, None, "Z00C"),
+    /* 26 */ ("SLA [HL]", 2, 4, None, "Z00C"),
+    /* 27 */ ("SLA A", 2, 2, None, "Z00C"),
+    /* 28 */ ("SRA B", 2, 2, None, "Z00C"),
+    /* 29 */ ("SRA C", 2, 2, None, "Z00C"),
+    /* 2A */ ("SRA D", 2, 2, None, "Z00C"),
+    /* 2B */ ("SRA E", 2, 2, None, "Z00C"),
+    /* 2C */ ("SRA H", 2, 2, None, "Z00C"),
+    /* 2D */ ("SRA L", 2, 2, None, "Z00C"),
+    /* 2E */ ("SRA [HL]", 2, 4, None, "Z00C"),
+    /* 2F */ ("SRA A", 2, 2, None, "Z00C"),
+    /* 30 */ ("SWAP B", 2, 2, None, "Z000"),
+    /* 31 */ ("SWAP C", 2, 2, None, "Z000"),
+    /* 32 */ ("SWAP D", 2, 2, None, "Z000"),
+    /* 33 */ ("SWAP E", 2, 2, None, "Z000"),
+    /* 34 */ ("SWAP H", 2, 2, None, "Z000"),
+    /* 35 */ ("SWAP L", 2, 2, None, "Z000"),
+    /* 36 */ ("SWAP [HL]", 2, 4, None, "Z000"),
+    /* 37 */ ("SWAP A", 2, 2, None, "Z000"),
+    /* 38 */ ("SRL B", 2, 2, None, "Z00C"),
+    /* 39 */ ("SRL C", 2, 2, None, "Z00C"),
+    /* 3A */ ("SRL D", 2, 2, None, "Z00C"),
+    /* 3B */ ("SRL E", 2, 2, None, "Z00C"),
+    /* 3C */ ("SRL H", 2, 2, None, "Z00C"),
+    /* 3D */ ("SRL L", 2, 2, None, "Z00C"),
+    /* 3E */ ("SRL [HL]", 2, 4, None, "Z00C"),
+    /* 3F */ ("SRL A", 2, 2, None, "Z00C"),
+    /* 40 */ ("BIT 0, B", 2, 2, None, "Z01-"),
+    /* 41 */ ("BIT 0, C", 2, 2, None, "Z01-"),
+    /* 42 */ ("BIT 0, D", 2, 2, None, "Z01-"),
+    /* 43 */ ("BIT 0, E", 2, 2, None, "Z01-"),
+    /* 44 */ ("BIT 0, H", 2, 2, None, "Z01-"),
+    /* 45 */ ("BIT 0, L", 2, 2, None, "Z01-"),
+    /* 46 */ ("BIT 0, [HL]", 2, 3, None, "Z01-"),
+    /* 47 */ ("BIT 0, A", 2, 2, None, "Z01-"),
+    /* 48 */ ("BIT 1, B", 2, 2, None, "Z01-"),
+    /* 49 */ ("BIT 1, C", 2, 2, None, "Z01-"),
+    /* 4A */ ("BIT 1, D", 2, 2, None, "Z01-"),
+    /* 4B */ ("BIT 1, E", 2, 2, None, "Z01-"),
+    /* 4C */ ("BIT 1, H", 2, 2, None, "Z01-"),
+    /* 4D */ ("BIT 1, L", 2, 2, None, "Z01-"),
+    /* 4E */ ("BIT 1, [HL]", 2, 3, None, "Z01-"),
+    /* 4F */ ("BIT 1, A", 2, 2, None, "Z01-"),
+    /* 50 */ ("BIT 2, B", 2, 2, None, "Z01-"),
+    /* 51 */ ("BIT 2, C", 2, 2, None, "Z01-"),
+    /* 52 */ ("BIT 2, D", 2, 2, None, "Z01-"),
+    /* 53 */ ("BIT 2, E", 2, 2, None, "Z01-"),
+    /* 54 */ ("BIT 2, H", 2, 2, None, "Z01-"),
+    /* 55 */ ("BIT 2, L", 2, 2, None, "Z01-"),
+    /* 56 */ ("BIT 2, [HL]", 2, 3, None, "Z01-"),
+    /* 57 */ ("BIT 2, A", 2, 2, None, "Z01-"),
+    /* 58 */ ("BIT 3, B", 2, 2, None, "Z01-"),
+    /* 59 */ ("BIT 3, C", 2, 2, None, "Z01-"),
+    /* 5A */ ("BIT 3, D", 2, 2, None, "Z01-"),
+    /* 5B */ ("BIT 3, E", 2, 2, None, "Z01-"),
+    /* 5C */ ("BIT 3, H", 2, 2, None, "Z01-"),
+    /* 5D */ ("BIT 3, L", 2, 2, None, "Z01-"),
+    /* 5E */ ("BIT 3, [HL]", 2, 3, None, "Z01-"),
+    /* 5F */ ("BIT 3, A", 2, 2, None, "Z01-"),
+    /* 60 */ ("BIT 4, B", 2, 2, None, "Z01-"),
+    /* 61 */ ("BIT 4, C", 2, 2, None, "Z01-"),
+    /* 62 */ ("BIT 4, D", 2, 2, None, "Z01-"),
+    /* 63 */ ("BIT 4, E", 2, 2, None, "Z01-"),
+    /* 64 */ ("BIT 4, H", 2, 2, None, "Z01-"),
+    /* 65 */ ("BIT 4, L", 2, 2, None, "Z01-"),
+    /* 66 */ ("BIT 4, [HL]", 2, 3, None, "Z01-"),
+    /* 67 */ ("BIT 4, A", 2, 2, None, "Z01-"),
+    /* 68 */ ("BIT 5, B", 2, 2, None, "Z01-"),
+    /* 69 */ ("BIT 5, C", 2, 2, None, "Z01-"),
+    /* 6A */ ("BIT 5, D", 2, 2, None, "Z01-"),
+    /* 6B */ ("BIT 5, E", 2, 2, None, "Z01-"),
+    /* 6C */ ("BIT 5, H", 2, 2, None, "Z01-"),
+    /* 6D */ ("BIT 5, L", 2, 2, None, "Z01-"),
+    /* 6E */ ("BIT 5, [HL]", 2, 3, None, "Z01-"),
+    /* 6F */ ("BIT 5, A", 2, 2, None, "Z01-"),
+    /* 70 */ ("BIT 6, B", 2, 2, None, "Z01-"),
+    /* 71 */ ("BIT 6, C", 2, 2, None, "Z01-"),
+    /* 72 */ ("BIT 6, D", 2, 2, None, "Z01-"),
+    /* 73 */ ("BIT 6, E", 2, 2, None, "Z01-"),
+    /* 74 */ ("BIT 6, H", 2, 2, None, "Z01-"),
+    /* 75 */ ("BIT 6, L", 2, 2, None, "Z01-"),
+    /* 76 */ ("BIT 6, [HL]", 2, 3, None, "Z01-"),
+    /* 77 */ ("BIT 6, A", 2, 2, None, "Z01-"),
+    /* 78 */ ("BIT 7, B", 2, 2, None, "Z01-"),
+    /* 79 */ ("BIT 7, C", 2, 2, None, "Z01-"),
+    /* 7A */ ("BIT 7, D", 2, 2, None, "Z01-"),
+    /* 7B */ ("BIT 7, E", 2, 2, None, "Z01-"),
+    /* 7C */ ("BIT 7, H", 2, 2, None, "Z01-"),
+    /* 7D */ ("BIT 7, L", 2, 2, None, "Z01-"),
+    /* 7E */ ("BIT 7, [HL]", 2, 3, None, "Z01-"),
+    /* 7F */ ("BIT 7, A", 2, 2, None, "Z01-"),
+    /* 80 */ ("RES 0, B", 2, 2, None, "----"),
+    /* 81 */ ("RES 0, C", 2, 2, None, "----"),
+    /* 82 */ ("RES 0, D", 2, 2, None, "----"),
+    /* 83 */ ("RES 0, E", 2, 2, None, "----"),
+    /* 84 */ ("RES 0, H", 2, 2, None, "----"),
+    /* 85 */ ("RES 0, L", 2, 2, None, "----"),
+    /* 86 */ ("RES 0, [HL]", 2, 4, None, "----"),
+    /* 87 */ ("RES 0, A", 2, 2, None, "----"),
+    /* 88 */ ("RES 1, B", 2, 2, None, "----"),
+    /* 89 */ ("RES 1, C", 2, 2, None, "----"),
+    /* 8A */ ("RES 1, D", 2, 2, None, "----"),
+    /* 8B */ ("RES 1, E", 2, 2, None, "----"),
+    /* 8C */ ("RES 1, H", 2, 2, None, "----"),
+    /* 8D */ ("RES 1, L", 2, 2, None, "----"),
+    /* 8E */ ("RES 1, [HL]", 2, 4, None, "----"),
+    /* 8F */ ("RES 1, A", 2, 2, None, "----"),
+    /* 90 */ ("RES 2, B", 2, 2, None, "----"),
+    /* 91 */ ("RES 2, C", 2, 2, None, "----"),
+    /* 92 */ ("RES 2, D", 2, 2, None, "----"),
+    /* 93 */ ("RES 2, E", 2, 2, None, "----"),
+    /* 94 */ ("RES 2, H", 2, 2, None, "----"),
+    /* 95 */ ("RES 2, L", 2, 2, None, "----"),
+    /* 96 */ ("RES 2, [HL]", 2, 4, None, "----"),
+    /* 97 */ ("RES 2, A", 2, 2, None, "----"),
+    /* 98 */ ("RES 3, B", 2, 2, None, "----"),
+    /* 99 */ ("RES 3, C", 2, 2, None, "----"),
+    /* 9A */ ("RES 3, D", 2, 2, None, "----"),
+    /* 9B */ ("RES 3, E", 2, 2, None, "----"),
+    /* 9C */ ("RES 3, H", 2, 2, None, "----"),
+    /* 9D */ ("RES 3, L", 2, 2, None, "----"),
+    /* 9E */ ("RES 3, [HL]", 2, 4, None, "----"),
+    /* 9F */ ("RES 3, A", 2, 2, None, "----"),
+    /* A0 */ ("RES 4, B", 2, 2, None, "----"),
+    /* A1 */ ("RES 4, C", 2, 2, None, "----"),
+    /* A2 */ ("RES 4, D", 2, 2, None, "----"),
+    /* A3 */ ("RES 4, E", 2, 2, None, "----"),
+    /* A4 */ ("RES 4, H", 2, 2, None, "----"),
+    /* A5 */ ("RES 4, L", 2, 2, None, "----"),
+    /* A6 */ ("RES 4, [HL]", 2, 4, None, "----"),
+    /* A7 */ ("RES 4, A", 2, 2, None, "----"),
+    /* A8 */ ("RES 5, B", 2, 2, None, "----"),
+    /* A9 */ ("RES 5, C", 2, 2, None, "----"),
+    /* AA */ ("RES 5, D", 2, 2, None, "----"),
+    /* AB */ ("RES 5, E", 2, 2, None, "----"),
+    /* AC */ ("RES 5, H", 2, 2, None, "----"),
+    /* AD */ ("RES 5, L", 2, 2, None, "----"),
+    /* AE */ ("RES 5, [HL]", 2, 4, None, "----"),
+    /* AF */ ("RES 5, A", 2, 2, None, "----"),
+    /* B0 */ ("RES 6, B", 2, 2, None, "----"),
+    /* B1 */ ("RES 6, C", 2, 2, None, "----"),
+    /* B2 */ ("RES 6, D", 2, 2, None, "----"),
+    /* B3 */ ("RES 6, E", 2, 2, None, "----"),
+    /* B4 */ ("RES 6, H", 2, 2, None, "----"),
+    /* B5 */ ("RES 6, L", 2, 2, None, "----"),
+    /* B6 */ ("RES 6, [HL]", 2, 4, None, "----"),
+    /* B7 */ ("RES 6, A", 2, 2, None, "----"),
+    /* B8 */ ("RES 7, B", 2, 2, None, "----"),
+    /* B9 */ ("RES 7, C", 2, 2, None, "----"),
+    /* BA */ ("RES 7, D", 2, 2, None, "----"),
+    /* BB */ ("RES 7, E", 2, 2, None, "----"),
+    /* BC */ ("RES 7, H", 2, 2, None, "----"),
+    /* BD */ ("RES 7, L", 2, 2, None, "----"),
+    /* BE */ ("RES 7, [HL]", 2, 4, None, "----"),
+    /* BF */ ("RES 7, A", 2, 2, None, "----"),
+    /* C0 */ ("SET 0, B", 2, 2, None, "----"),
+    /* C1 */ ("SET 0, C", 2, 2, None, "----"),
+    /* C2 */ ("SET 0, D", 2, 2, None, "----"),
+    /* C3 */ ("SET 0, E", 2, 2, None, "----"),
+    /* C4 */ ("SET 0, H", 2, 2, None, "----"),
+    /* C5 */ ("SET 0, L", 2, 2, None, "----"),
+    /* C6 */ ("SET 0, [HL]", 2, 4, None, "----"),
+    /* C7 */ ("SET 0, A", 2, 2, None, "----"),
+    /* C8 */ ("SET 1, B", 2, 2, None, "----"),
+    /* C9 */ ("SET 1, C", 2, 2, None, "----"),
+    /* CA */ ("SET 1, D", 2, 2, None, "----"),
+    /* CB */ ("SET 1, E", 2, 2, None, "----"),
+    /* CC */ ("SET 1, H", 2, 2, None, "----"),
+    /* CD */ ("SET 1, L", 2, 2, None, "----"),
+    /* CE */ ("SET 1, [HL]", 2, 4, None, "----"),
+    /* CF */ ("SET 1, A", 2, 2, None, "----"),
+    /* D0 */ ("SET 2, B", 2, 2, None, "----"),
+    /* D1 */ ("SET 2, C", 2, 2, None, "----"),
+    /* D2 */ ("SET 2, D", 2, 2, None, "----"),
+    /* D3 */ ("SET 2, E", 2, 2, None, "----"),
+    /* D4 */ ("SET 2, H", 2, 2, None, "----"),
+    /* D5 */ ("SET 2, L", 2, 2, None, "----"),
+    /* D6 */ ("SET 2, [HL]", 2, 4, None, "----"),
+    /* D7 */ ("SET 2, A", 2, 2, None, "----"),
+    /* D8 */ ("SET 3, B", 2, 2, None, "----"),
+    /* D9 */ ("SET 3, C", 2, 2, None, "----"),
+    /* DA */ ("SET 3, D", 2, 2, None, "----"),
+    /* DB */ ("SET 3, E", 2, 2, None, "----"),
+    /* DC */ ("SET 3, H", 2, 2, None, "----"),
+    /* DD */ ("SET 3, L", 2, 2, None, "----"),
+    /* DE */ ("SET 3, [HL]", 2, 4, None, "----"),
+    /* DF */ ("SET 3, A", 2, 2, None, "----"),
+    /* E0 */ ("SET 4, B", 2, 2, None, "----"),
+    /* E1 */ ("SET 4, C", 2, 2, None, "----"),
+    /* E2 */ ("SET 4, D", 2, 2, None, "----"),
+    /* E3 */ ("SET 4, E", 2, 2, None, "----"),
+    /* E4 */ ("SET 4, H", 2, 2, None, "----"),
+    /* E5 */ ("SET 4, L", 2, 2, None, "----"),
+    /* E6 */ ("SET 4, [HL]", 2, 4, None, "----"),
+    /* E7 */ ("SET 4, A", 2, 2, None, "----"),
+    /* E8 */ ("SET 5, B", 2, 2, None, "----"),
+    /* E9 */ ("SET 5, C", 2, 2, None, "----"),
+    /* EA */ ("SET 5, D", 2, 2, None, "----"),
+    /* EB */ ("SET 5, E", 2, 2, None, "----"),
+    /* EC */ ("SET 5, H", 2, 2, None, "----"),
+    /* ED */ ("SET 5, L", 2, 2, None, "----"),
+    /* EE */ ("SET 5, [HL]", 2, 4, None, "----"),
+    /* EF */ ("SET 5, A", 2, 2, None, "----"),
+    /* F0 */ ("SET 6, B", 2, 2, None, "----"),
+    /* F1 */ ("SET 6, C", 2, 2, None, "----"),
+    /* F2 */ ("SET 6, D", 2, 2, None, "----"),
+    /* F3 */ ("SET 6, E", 2, 2, None, "----"),
+    /* F4 */ ("SET 6, H", 2, 2, None, "----"),
+    /* F5 */ ("SET 6, L", 2, 2, None, "----"),
+    /* F6 */ ("SET 6, [HL]", 2, 4, None, "----"),
+    /* F7 */ ("SET 6, A", 2, 2, None, "----"),
+    /* F8 */ ("SET 7, B", 2, 2, None, "----"),
+    /* F9 */ ("SET 7, C", 2, 2, None, "----"),
+    /* FA */ ("SET 7, D", 2, 2, None, "----"),
+    /* FB */ ("SET 7, E", 2, 2, None, "----"),
+    /* FC */ ("SET 7, H", 2, 2, None, "----"),
+    /* FD */ ("SET 7, L", 2, 2, None, "----"),
+    /* FE */ ("SET 7, [HL]", 2, 4, None, "----"),
+    /* FF */ ("SET 7, A", 2, 2, None, "----"),
+];
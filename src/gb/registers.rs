@@ -74,6 +74,20 @@ pub enum R16Stk {
     AF,
 }
 
+impl TryFrom<u8> for R16Stk {
+    type Error = ();
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(R16Stk::BC),
+            1 => Ok(R16Stk::DE),
+            2 => Ok(R16Stk::HL),
+            3 => Ok(R16Stk::AF),
+            _ => Err(()),
+        }
+    }
+}
+
 #[repr(u8)]
 pub enum R16Mem {
     BC,
@@ -105,6 +119,20 @@ pub enum Cond {
     C,
 }
 
+impl TryFrom<u8> for Cond {
+    type Error = ();
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Cond::NZ),
+            1 => Ok(Cond::Z),
+            2 => Ok(Cond::NC),
+            3 => Ok(Cond::C),
+            _ => Err(()),
+        }
+    }
+}
+
 enum FlagBytePositions {
     Zero = 7,
     Subtract = 6,
@@ -129,7 +157,7 @@ impl std::ops::Shl<FlagBytePositions> for u8 {
 }
 
 // 0x11110000 -> where the first four bits correspond to `zshc` in the struct.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct FlagsRegisters {
     pub z: bool, // Zero
     pub s: bool, // Subtract
@@ -173,6 +201,12 @@ impl std::convert::From<u8> for FlagsRegisters {
     }
 }
 
+impl Default for Registers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Registers {
     pub fn new() -> Registers {
         Registers {
@@ -189,6 +223,18 @@ impl Registers {
         }
     }
 
+    /* The low nibble of F does not exist in hardware, so going through the
+     * FlagsRegisters conversions always reads and writes it as zero.
+     */
+    pub fn af(&self) -> u16 {
+        (self.a as u16) << 8 | u8::from(self.f) as u16
+    }
+
+    pub fn set_af(&mut self, val: u16) {
+        self.a = ((val & 0xFF00) >> 8) as u8;
+        self.f = FlagsRegisters::from((val & 0x00FF) as u8);
+    }
+
     pub fn bc(&self) -> u16 {
         (self.b as u16) << 8 | self.c as u16
     }
@@ -217,6 +263,6 @@ impl Registers {
     }
 
     pub fn advance_pc(&mut self) {
-        self.pc += 1;
+        self.pc = self.pc.wrapping_add(1);
     }
 }
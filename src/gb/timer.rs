@@ -0,0 +1,40 @@
+/* The timer is driven by a free-running 16-bit counter that advances every
+ * T-cycle (four times per M-cycle). DIV is just its upper byte, so any
+ * write to DIV clears the whole counter.
+ *
+ * TODO: TIMA, TMA and TAC, which tap bits of this same counter.
+ */
+#[derive(Debug)]
+pub struct Timer {
+    counter: u16,
+}
+
+impl Default for Timer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Timer {
+    pub fn new() -> Timer {
+        Timer { counter: 0x0000 }
+    }
+
+    /* Advances the timer by one M-cycle. */
+    pub fn tick(&mut self) {
+        self.counter = self.counter.wrapping_add(4);
+    }
+
+    pub fn read_div(&self) -> u8 {
+        (self.counter >> 8) as u8
+    }
+
+    pub fn write_div(&mut self) {
+        self.counter = 0x0000;
+    }
+
+    /* Sets the whole internal counter, e.g. to where a boot ROM leaves it. */
+    pub fn set_counter(&mut self, counter: u16) {
+        self.counter = counter;
+    }
+}
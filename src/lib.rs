@@ -0,0 +1,5 @@
+// The instruction and register enums intentionally mirror the opcode
+// mnemonics (LD, HALT, RLCA, ...), so allow the all-caps names crate-wide.
+#![allow(clippy::upper_case_acronyms)]
+
+pub mod gb;
@@ -1,27 +1,56 @@
-mod gb;
-
-use std::fs;
-use crate::gb::cpu::CPU;
+use std::{env, fs, process};
+use crabbyboy::gb::boot::BootRom;
+use crabbyboy::gb::cartridge::Cartridge;
+use crabbyboy::gb::cpu::CPU;
+use crabbyboy::gb::model::Model;
 
 fn main() {
-    // Read the boot rom
-    let bin = fs::read("./roms/dmg_boot.bin").unwrap();
-    let _boot_rom = String::new();
+    // Read the boot rom, if there is a usable one
+    let boot_rom = fs::read("./roms/dmg_boot.bin")
+        .ok()
+        .and_then(|bin| BootRom::new(bin).ok());
 
     // Make a new CPU
     let mut cpu = CPU::new();
 
-    // Make an iterator out of the binary file
-    let iterator = bin.iter();
-
-    // Load the iterator into memory
-    cpu.memory_bus.load_rom(iterator);
+    // The cartridge, if any, goes underneath the boot rom
+    if let Some(path) = env::args().nth(1) {
+        let rom = match fs::read(&path) {
+            Ok(rom) => rom,
+            Err(err) => {
+                eprintln!("error: could not read {path}: {err}");
+                process::exit(1);
+            }
+        };
+        let cartridge = match Cartridge::new(rom) {
+            Ok(cartridge) => cartridge,
+            Err(err) => {
+                eprintln!("error: {err}");
+                process::exit(1);
+            }
+        };
+        if let Err(err) = cartridge.verify() {
+            eprintln!("warning: {err}");
+        }
+        if let Err(err) = cpu.memory_bus.load_cartridge(cartridge) {
+            eprintln!("error: {err}");
+            process::exit(1);
+        }
+    }
+    match boot_rom {
+        Some(boot_rom) => cpu.memory_bus.load_boot_rom(boot_rom),
+        // Without one, start where the DMG boot rom would have left off
+        None => cpu.skip_boot(Model::Dmg),
+    }
 
     let mut end_found = false;
     let mut instr_count: u16 = 0;
 
     while !end_found {
-        cpu.cycle();
+        if let Err(err) = cpu.cycle() {
+            eprintln!("{err}");
+            end_found = true;
+        }
         instr_count += 1;
         if cpu.end || instr_count > 260 {
             end_found = true;
@@ -0,0 +1,45 @@
+/*
+ * Word access on the buses the CPU can be plugged into. Words are
+ * little-endian and their address wraps from 0xFFFF back to 0x0000.
+ */
+
+use crabbyboy::gb::bus::{Bus, FlatRam};
+use crabbyboy::gb::mmu::MemoryManagementUnit as MMU;
+
+#[test]
+fn flat_ram_words_are_little_endian() {
+    let mut ram = FlatRam::new();
+    ram.write_word(0xC000, 0xBEEF);
+    assert_eq!(ram.read(0xC000), 0xEF);
+    assert_eq!(ram.read(0xC001), 0xBE);
+    assert_eq!(ram.read_word(0xC000), 0xBEEF);
+}
+
+#[test]
+fn flat_ram_words_wrap_at_0xffff() {
+    let mut ram = FlatRam::new();
+    ram.write_word(0xFFFF, 0x1234);
+    assert_eq!(ram.read(0xFFFF), 0x34);
+    assert_eq!(ram.read(0x0000), 0x12);
+    assert_eq!(ram.read_word(0xFFFF), 0x1234);
+}
+
+#[test]
+fn mmu_words_are_little_endian() {
+    let mut mmu = MMU::new();
+    mmu.write_word(0xC000, 0xBEEF);
+    assert_eq!(mmu.read_byte(0xC000), 0xEF);
+    assert_eq!(mmu.read_byte(0xC001), 0xBE);
+    assert_eq!(mmu.read_word(0xC000), 0xBEEF);
+}
+
+#[test]
+fn mmu_words_wrap_at_0xffff() {
+    // 0xFFFF is IE and 0x0000 is ROM, so only the low byte sticks.
+    let mut mmu = MMU::new();
+    mmu.load_rom([0x12].iter());
+    mmu.write_word(0xFFFF, 0xAB1F);
+    assert_eq!(mmu.interrupts.read_enable(), 0x1F);
+    assert_eq!(mmu.read_byte(0x0000), 0x12);
+    assert_eq!(mmu.read_word(0xFFFF), 0x121F);
+}
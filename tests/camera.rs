@@ -0,0 +1,154 @@
+/*
+ * The Pocket Camera cartridge, fed with still images instead of a sensor.
+ */
+
+use crabbyboy::gb::mbc::{
+    CameraImage, CameraImageError, MemoryBankController, PocketCamera, IMAGE_HEIGHT, IMAGE_WIDTH,
+};
+use std::{env, fs};
+
+/* Maps the sensor registers and sets up a neutral exposure with the same
+ * thresholds, 0x40, 0x80 and 0xC0, all over the dithering matrix.
+ */
+fn camera_with(image: CameraImage) -> PocketCamera {
+    let mut camera = PocketCamera::new(vec![0; 0x100000]);
+    camera.set_camera_image(image);
+    camera.write_rom(0x4000, 0x10);
+    camera.write_ram(0xA002, 0x10);
+    camera.write_ram(0xA003, 0x00);
+    for cell in 0..16 {
+        for (n, threshold) in [0x40, 0x80, 0xC0].into_iter().enumerate() {
+            camera.write_ram(0xA006 + cell * 3 + n as u16, threshold);
+        }
+    }
+    camera
+}
+
+fn capture(camera: &mut PocketCamera) {
+    camera.write_rom(0x4000, 0x10);
+    camera.write_ram(0xA000, 0x01);
+    assert_eq!(camera.read_ram(0xA000) & 0x01, 0);
+    camera.write_rom(0x4000, 0x00);
+}
+
+/* The shade of one captured pixel, decoded from the 2bpp tiles in RAM. */
+fn shade(camera: &PocketCamera, x: usize, y: usize) -> u8 {
+    let tile = (y / 8) * 16 + x / 8;
+    let row = 0xA100 + (tile * 16 + (y % 8) * 2) as u16;
+    let bit = 7 - x % 8;
+    (camera.read_ram(row) >> bit & 1) | (camera.read_ram(row + 1) >> bit & 1) << 1
+}
+
+/* A sensor-sized image with every pixel set by `shade_at(x, y)`. */
+fn image(shade_at: impl Fn(usize, usize) -> u8) -> CameraImage {
+    let pixels: Vec<u8> = (0..IMAGE_WIDTH * IMAGE_HEIGHT)
+        .map(|n| shade_at(n % IMAGE_WIDTH, n / IMAGE_WIDTH))
+        .collect();
+    CameraImage::from_grayscale(IMAGE_WIDTH, IMAGE_HEIGHT, &pixels).unwrap()
+}
+
+fn flat(val: u8) -> CameraImage {
+    image(|_, _| val)
+}
+
+#[test]
+fn capture_dithers_into_tiles() {
+    // Four vertical bands from black to white.
+    let mut camera = camera_with(image(|x, _| (x / 32) as u8 * 0x50));
+    capture(&mut camera);
+    assert_eq!(shade(&camera, 0, 0), 3);
+    assert_eq!(shade(&camera, 40, 50), 2);
+    assert_eq!(shade(&camera, 70, 100), 1);
+    assert_eq!(shade(&camera, 127, 111), 0);
+    // Nothing is written past the 16 x 14 tiles.
+    assert_eq!(camera.read_ram(0xAF00), 0x00);
+}
+
+#[test]
+fn exposure_and_inversion() {
+    let mut camera = camera_with(flat(0x60));
+    capture(&mut camera);
+    assert_eq!(shade(&camera, 10, 10), 2);
+
+    // Doubling the exposure time brightens 0x60 to 0xC0.
+    camera.write_rom(0x4000, 0x10);
+    camera.write_ram(0xA002, 0x20);
+    capture(&mut camera);
+    assert_eq!(shade(&camera, 10, 10), 0);
+
+    camera.write_rom(0x4000, 0x10);
+    camera.write_ram(0xA004, 0x08);
+    capture(&mut camera);
+    assert_eq!(shade(&camera, 10, 10), 3);
+}
+
+#[test]
+fn edge_enhancement_brings_out_edges() {
+    let mut camera = camera_with(image(|x, _| if x < 64 { 0x90 } else { 0x70 }));
+    capture(&mut camera);
+    assert_eq!([shade(&camera, 63, 0), shade(&camera, 64, 0)], [1, 2]);
+
+    // Horizontal enhancement at a ratio of 2.
+    camera.write_rom(0x4000, 0x10);
+    camera.write_ram(0xA001, 0x20);
+    camera.write_ram(0xA004, 0x40);
+    capture(&mut camera);
+    assert_eq!([shade(&camera, 63, 0), shade(&camera, 64, 0)], [0, 3]);
+    assert_eq!([shade(&camera, 10, 0), shade(&camera, 100, 0)], [1, 2]);
+}
+
+#[test]
+fn ram_banks_and_write_enable() {
+    let mut camera = PocketCamera::new(vec![0; 0x100000]);
+    camera.write_rom(0x4000, 0x0F);
+    camera.write_ram(0xBFFF, 0x42);
+    assert_eq!(camera.read_ram(0xBFFF), 0x00);
+    camera.write_rom(0x0000, 0x0A);
+    camera.write_ram(0xBFFF, 0x42);
+    camera.write_rom(0x0000, 0x00);
+    assert_eq!(camera.read_ram(0xBFFF), 0x42);
+    assert_eq!(camera.save_data().len(), 0x20000);
+}
+
+#[test]
+fn reads_pgm_images() {
+    // Both encodings of the same 0x00, 0x55, 0xAA, 0xFF repeating pattern.
+    let levels = [0, 5, 10, 15].repeat(IMAGE_WIDTH * IMAGE_HEIGHT / 4);
+    let mut plain = b"P2\n# a comment\n128 112\n15\n".to_vec();
+    for level in &levels {
+        plain.extend(format!("{level}\n").bytes());
+    }
+    let mut binary = b"P5 128 112 255\n".to_vec();
+    binary.extend(levels.iter().map(|level| level * 0x11));
+
+    let plain = CameraImage::from_pgm(&plain).unwrap();
+    assert_eq!(plain, CameraImage::from_pgm(&binary).unwrap());
+    assert_eq!(plain, image(|x, _| (x % 4) as u8 * 0x55));
+
+    let path = env::temp_dir().join(format!("crabbyboy-camera-{}.pgm", std::process::id()));
+    fs::write(&path, &binary).unwrap();
+    let opened = CameraImage::open(&path);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(opened.unwrap(), plain);
+}
+
+#[test]
+fn rejects_unusable_images() {
+    assert!(matches!(
+        CameraImage::from_pgm(b"P6 128 112 255\n\0\0\0"),
+        Err(CameraImageError::Format)
+    ));
+    // Header dimensions are checked before anything is allocated for them.
+    assert!(matches!(
+        CameraImage::from_pgm(b"P2 4294967296 4294967296 255 1"),
+        Err(CameraImageError::Format)
+    ));
+    assert!(matches!(
+        CameraImage::from_pgm(b"P5 2 2 255\n\0\0\0\0"),
+        Err(CameraImageError::Dimensions { width: 2, height: 2 })
+    ));
+    assert!(matches!(
+        CameraImage::from_grayscale(IMAGE_WIDTH, IMAGE_HEIGHT, &[0; 3]),
+        Err(CameraImageError::Size { expected: 0x3800, actual: 3 })
+    ));
+}
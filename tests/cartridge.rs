@@ -0,0 +1,127 @@
+/*
+ * Parsing and checking the cartridge header at 0x0100-0x014F.
+ */
+
+use crabbyboy::gb::cartridge::{
+    Cartridge, CartridgeError, CgbSupport, Controller, Licensee, NINTENDO_LOGO,
+};
+use crabbyboy::gb::mmu::MemoryManagementUnit as MMU;
+
+/* A ROM with a valid header for the given type, ROM and RAM size codes. */
+fn rom(title: &[u8], cartridge_type: u8, rom_size: u8, ram_size: u8) -> Vec<u8> {
+    let mut rom = vec![0x00; 0x8000 << rom_size];
+    rom[0x0104..0x0134].copy_from_slice(&NINTENDO_LOGO);
+    rom[0x0134..0x0134 + title.len()].copy_from_slice(title);
+    rom[0x0147] = cartridge_type;
+    rom[0x0148] = rom_size;
+    rom[0x0149] = ram_size;
+    rom[0x014B] = 0x01;
+    fix_checksums(&mut rom);
+    rom
+}
+
+fn fix_checksums(rom: &mut [u8]) {
+    rom[0x014D] = rom[0x0134..0x014D]
+        .iter()
+        .fold(0u8, |sum, byte| sum.wrapping_sub(*byte).wrapping_sub(1));
+    rom[0x014E] = 0x00;
+    rom[0x014F] = 0x00;
+    let global = rom.iter().fold(0u16, |sum, byte| sum.wrapping_add(*byte as u16));
+    rom[0x014E] = (global >> 8) as u8;
+    rom[0x014F] = global as u8;
+}
+
+#[test]
+fn parses_header_fields() {
+    let mut image = rom(b"TETRIS", 0x03, 0x01, 0x02);
+    image[0x014C] = 0x01;
+    fix_checksums(&mut image);
+    let cartridge = Cartridge::new(image).unwrap();
+    assert_eq!(cartridge.title, "TETRIS");
+    assert_eq!(cartridge.manufacturer, None);
+    assert_eq!(cartridge.cgb, CgbSupport::None);
+    assert!(!cartridge.sgb);
+    assert_eq!(cartridge.licensee, Licensee::Old(0x01));
+    assert_eq!(cartridge.cartridge_type.controller, Controller::Mbc1);
+    assert!(cartridge.cartridge_type.ram && cartridge.cartridge_type.battery);
+    assert_eq!(cartridge.rom_size, 0x10000);
+    assert_eq!(cartridge.ram_size, 0x2000);
+    assert!(cartridge.japanese);
+    assert_eq!(cartridge.version, 0x01);
+    assert_eq!(cartridge.verify(), Ok(()));
+}
+
+#[test]
+fn parses_cgb_header_fields() {
+    let mut image = rom(b"POKEMON_SLVAAXE", 0x10, 0x00, 0x03);
+    image[0x0143] = 0x80;
+    image[0x0144..0x0146].copy_from_slice(b"01");
+    image[0x0146] = 0x03;
+    image[0x014A] = 0x01;
+    image[0x014B] = 0x33;
+    fix_checksums(&mut image);
+    let cartridge = Cartridge::new(image).unwrap();
+    assert_eq!(cartridge.title, "POKEMON_SLV");
+    assert_eq!(cartridge.manufacturer.as_deref(), Some("AAXE"));
+    assert_eq!(cartridge.cgb, CgbSupport::Compatible);
+    assert!(cartridge.sgb);
+    assert!(!cartridge.japanese);
+    assert_eq!(cartridge.licensee, Licensee::New("01".to_string()));
+    assert!(cartridge.cartridge_type.timer);
+}
+
+#[test]
+fn rejects_bad_images() {
+    assert_eq!(Cartridge::new(vec![0; 0x100]).unwrap_err(), CartridgeError::Truncated(0x100));
+
+    let image = rom(b"BAD", 0x23, 0x00, 0x00);
+    assert_eq!(Cartridge::new(image).unwrap_err(), CartridgeError::UnknownType(0x23));
+
+    let mut image = rom(b"SHORT", 0x01, 0x02, 0x00);
+    image.truncate(0x10000);
+    assert_eq!(
+        Cartridge::new(image).unwrap_err(),
+        CartridgeError::RomSizeMismatch {
+            header: 0x20000,
+            actual: 0x10000
+        }
+    );
+}
+
+#[test]
+fn verifies_logo_and_checksums() {
+    let mut image = rom(b"LOGO", 0x00, 0x00, 0x00);
+    image[0x0104] = 0x00;
+    assert_eq!(Cartridge::new(image).unwrap().verify(), Err(CartridgeError::Logo));
+
+    let mut image = rom(b"HEADER", 0x00, 0x00, 0x00);
+    image[0x014D] ^= 0xFF;
+    assert!(matches!(
+        Cartridge::new(image).unwrap().verify(),
+        Err(CartridgeError::HeaderChecksum { .. })
+    ));
+
+    let mut image = rom(b"GLOBAL", 0x00, 0x00, 0x00);
+    image[0x4000] = 0x01;
+    assert!(matches!(
+        Cartridge::new(image).unwrap().verify(),
+        Err(CartridgeError::GlobalChecksum { .. })
+    ));
+}
+
+#[test]
+fn mmu_picks_controller_from_header() {
+    let mut image = rom(b"RAM", 0x08, 0x00, 0x02);
+    image[0x0150] = 0xC3;
+    let mut mmu = MMU::new();
+    mmu.load_cartridge(Cartridge::new(image).unwrap()).unwrap();
+    assert_eq!(mmu.read_byte(0x0150), 0xC3);
+    mmu.set_byte(0xA000, 0x42);
+    assert_eq!(mmu.read_byte(0xA000), 0x42);
+
+    let image = rom(b"MMM", 0x0B, 0x00, 0x00);
+    assert_eq!(
+        mmu.load_cartridge(Cartridge::new(image).unwrap()),
+        Err(CartridgeError::Unsupported(Controller::Mmm01))
+    );
+}
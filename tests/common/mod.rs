@@ -0,0 +1,59 @@
+/*
+ * Helpers shared by the integration tests. Each test crate only uses some
+ * of them.
+ */
+#![allow(dead_code)]
+
+use crabbyboy::gb::bus::{Bus, FlatRam};
+use crabbyboy::gb::cpu::CPU;
+use crabbyboy::gb::mbc::Clock;
+use std::cell::Cell;
+use std::rc::Rc;
+
+/* A CPU on a flat RAM bus about to run `program` from 0xC000, with the stack
+ * at the top of work RAM.
+ */
+pub fn cpu_with_program(program: &[u8]) -> CPU<FlatRam> {
+    let mut ram = FlatRam::new();
+    for (offset, byte) in program.iter().enumerate() {
+        ram.write(0xC000 + offset as u16, *byte);
+    }
+    let mut cpu = CPU::with_bus(ram);
+    cpu.registers.pc = 0xC000;
+    cpu.registers.sp = 0xDFFE;
+    cpu
+}
+
+/* A ROM with every 16 KiB bank filled with its own number, so reads show
+ * which bank is mapped.
+ */
+pub fn numbered_rom(banks: usize) -> Vec<u8> {
+    (0..banks).flat_map(|bank| vec![bank as u8; 0x4000]).collect()
+}
+
+/* Like `numbered_rom`, for controllers with more than 256 banks: the bank
+ * number is written over and over, low byte first then high byte.
+ */
+pub fn wide_numbered_rom(banks: usize) -> Vec<u8> {
+    (0..banks)
+        .flat_map(|bank| [bank as u8, (bank >> 8) as u8].repeat(0x2000))
+        .collect()
+}
+
+/* A clock that only moves when told to. Clones share the same time, so a
+ * test can keep one and hand another to the cartridge.
+ */
+#[derive(Debug, Clone, Default)]
+pub struct TestClock(Rc<Cell<u64>>);
+
+impl TestClock {
+    pub fn advance(&self, seconds: u64) {
+        self.0.set(self.0.get() + seconds);
+    }
+}
+
+impl Clock for TestClock {
+    fn now(&self) -> u64 {
+        self.0.get()
+    }
+}
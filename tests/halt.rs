@@ -0,0 +1,39 @@
+/*
+ * HALT on a plain 64 KiB bus, without the rest of the Game Boy around it.
+ */
+
+mod common;
+
+use common::cpu_with_program;
+use crabbyboy::gb::bus::{Bus, IE_ADDRESS, IF_ADDRESS};
+use crabbyboy::gb::cpu::StepEvent;
+
+#[test]
+fn halt_bug_runs_the_next_byte_twice() {
+    // HALT; INC A with a VBlank pending and IME off.
+    let mut cpu = cpu_with_program(&[0x76, 0x3C]);
+    cpu.memory_bus.write(IE_ADDRESS, 0x01);
+    cpu.memory_bus.write(IF_ADDRESS, 0x01);
+
+    for _ in 0..3 {
+        let step = cpu.cycle().unwrap();
+        assert!(matches!(step.event, StepEvent::Executed { .. }));
+    }
+    assert_eq!(cpu.registers.a, 2);
+    assert_eq!(cpu.registers.pc, 0xC002);
+    // IME was off, so the interrupt is still waiting.
+    assert_eq!(cpu.memory_bus.read(IF_ADDRESS), 0x01);
+}
+
+#[test]
+fn halt_without_a_pending_interrupt_waits() {
+    let mut cpu = cpu_with_program(&[0x76, 0x3C]);
+    cpu.memory_bus.write(IE_ADDRESS, 0x01);
+
+    cpu.cycle().unwrap();
+    for _ in 0..4 {
+        assert_eq!(cpu.cycle().unwrap().event, StepEvent::Halted);
+    }
+    assert_eq!(cpu.registers.pc, 0xC001);
+    assert_eq!(cpu.registers.a, 0);
+}
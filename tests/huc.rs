@@ -0,0 +1,120 @@
+/*
+ * Hudson's HuC1 and HuC3 controllers and the infrared link they share with
+ * the CGB.
+ */
+
+mod common;
+
+use common::{numbered_rom, TestClock};
+use crabbyboy::gb::infrared::IrPort;
+use crabbyboy::gb::mbc::{HuC1, HuC3, MemoryBankController};
+use crabbyboy::gb::mmu::MemoryManagementUnit as MMU;
+
+/* Sends a HuC3 command and returns the response. */
+fn huc3_command(mbc: &mut HuC3, command: u8) -> u8 {
+    mbc.write_rom(0x0000, 0x0B);
+    mbc.write_ram(0xA000, command);
+    mbc.write_rom(0x0000, 0x0D);
+    assert_eq!(mbc.read_ram(0xA000) & 0x01, 0x01);
+    mbc.write_rom(0x0000, 0x0C);
+    mbc.read_ram(0xA000) & 0x0F
+}
+
+/* Reads `count` nibbles of clock memory starting at `index`. */
+fn huc3_read(mbc: &mut HuC3, index: u8, count: u8) -> u16 {
+    huc3_command(mbc, 0x40 | index & 0x0F);
+    huc3_command(mbc, 0x50 | index >> 4);
+    (0..count).fold(0, |val, n| val | (huc3_command(mbc, 0x10) as u16) << (n * 4))
+}
+
+#[test]
+fn huc1_switches_between_ram_and_ir() {
+    let mut mbc = HuC1::new(numbered_rom(64), 0x8000);
+    mbc.write_rom(0x2000, 0x3F);
+    assert_eq!(mbc.read_rom(0x4000), 0x3F);
+    mbc.write_rom(0x4000, 0x02);
+    mbc.write_ram(0xA000, 0x42);
+    assert_eq!(mbc.read_ram(0xA000), 0x42);
+
+    mbc.write_rom(0x0000, 0x0E);
+    assert_eq!(mbc.read_ram(0xA000), 0xC0);
+    mbc.write_ram(0xA000, 0x01);
+    mbc.write_rom(0x0000, 0x00);
+    assert_eq!(mbc.read_ram(0xA000), 0x42);
+}
+
+#[test]
+fn two_huc1_cartridges_see_each_other() {
+    let (a, b) = IrPort::pair();
+    let mut left = HuC1::new(numbered_rom(2), 0x2000);
+    let mut right = HuC1::new(numbered_rom(2), 0x2000);
+    left.set_ir_channel(Box::new(a));
+    right.set_ir_channel(Box::new(b));
+    left.write_rom(0x0000, 0x0E);
+    right.write_rom(0x0000, 0x0E);
+
+    left.write_ram(0xA000, 0x01);
+    assert_eq!(right.read_ram(0xA000), 0xC1);
+    assert_eq!(left.read_ram(0xA000), 0xC0);
+    left.write_ram(0xA000, 0x00);
+    assert_eq!(right.read_ram(0xA000), 0xC0);
+}
+
+#[test]
+fn huc3_talks_to_a_cgb() {
+    let (a, b) = IrPort::pair();
+    let mut mbc = HuC3::new(numbered_rom(2), 0x2000, Box::new(TestClock::default()));
+    mbc.set_ir_channel(Box::new(a));
+    let mut mmu = MMU::new();
+    mmu.infrared.connect(Box::new(b));
+
+    // The CGB only sees light with reading enabled, and then bit 1 drops.
+    mbc.write_rom(0x0000, 0x0E);
+    mbc.write_ram(0xA000, 0x01);
+    assert_eq!(mmu.read_byte(0xFF56), 0x3E);
+    mmu.set_byte(0xFF56, 0xC0);
+    assert_eq!(mmu.read_byte(0xFF56), 0xFC);
+
+    mmu.set_byte(0xFF56, 0xC1);
+    assert_eq!(mbc.read_ram(0xA000), 0xC1);
+}
+
+#[test]
+fn huc3_clock_counts_minutes_and_days() {
+    let clock = TestClock::default();
+    let mut mbc = HuC3::new(numbered_rom(2), 0x2000, Box::new(clock.clone()));
+    clock.advance(2 * 86400 + 90 * 60 + 59);
+    assert_eq!(huc3_read(&mut mbc, 0x00, 3), 90);
+    assert_eq!(huc3_read(&mut mbc, 0x03, 4), 2);
+
+    // Set the minute of the day to 0x123 through the write-and-advance command.
+    huc3_command(&mut mbc, 0x40);
+    huc3_command(&mut mbc, 0x50);
+    for nibble in [0x3, 0x2, 0x1] {
+        huc3_command(&mut mbc, 0x30 | nibble);
+    }
+    clock.advance(1);
+    assert_eq!(huc3_read(&mut mbc, 0x00, 3), 0x124);
+
+    huc3_command(&mut mbc, 0x6E);
+    assert_eq!(mbc.tone(), Some(0x0E));
+}
+
+#[test]
+fn huc3_save_catches_up_on_elapsed_time() {
+    let clock = TestClock::default();
+    clock.advance(1_700_000_000);
+    let mut mbc = HuC3::new(numbered_rom(2), 0x2000, Box::new(clock.clone()));
+    mbc.write_rom(0x0000, 0x0A);
+    mbc.write_ram(0xA000, 0x42);
+    let save = mbc.save_data();
+    assert_eq!(save.len(), 0x2000 + 17);
+
+    clock.advance(1441 * 60);
+    let mut mbc = HuC3::new(numbered_rom(2), 0x2000, Box::new(clock.clone()));
+    mbc.load_save_data(&save);
+    assert_eq!(huc3_read(&mut mbc, 0x00, 3), 1);
+    assert_eq!(huc3_read(&mut mbc, 0x03, 4), 1);
+    mbc.write_rom(0x0000, 0x00);
+    assert_eq!(mbc.read_ram(0xA000), 0x42);
+}
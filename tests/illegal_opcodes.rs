@@ -0,0 +1,47 @@
+/*
+ * The eleven unused opcodes hang the CPU for good.
+ */
+
+mod common;
+
+use common::cpu_with_program;
+use crabbyboy::gb::bus::{Bus, IE_ADDRESS, IF_ADDRESS};
+use crabbyboy::gb::cpu::CpuError;
+
+const ILLEGAL_OPCODES: [u8; 11] = [
+    0xD3, 0xDB, 0xDD, 0xE3, 0xE4, 0xEB, 0xEC, 0xED, 0xF4, 0xFC, 0xFD,
+];
+
+#[test]
+fn illegal_opcodes_lock_up_the_cpu() {
+    for opcode in ILLEGAL_OPCODES {
+        let mut cpu = cpu_with_program(&[opcode, 0x00]);
+        let address = 0xC000;
+
+        assert_eq!(cpu.cycle(), Err(CpuError::IllegalOpcode { address, opcode }));
+        assert!(cpu.is_locked());
+        assert_eq!(cpu.cycle(), Err(CpuError::Locked { address, opcode }));
+        assert_eq!(cpu.registers.pc, 0xC001);
+    }
+}
+
+#[test]
+fn interrupts_do_not_unlock_the_cpu() {
+    for opcode in ILLEGAL_OPCODES {
+        let mut cpu = cpu_with_program(&[opcode, 0x00]);
+        let address = 0xC000;
+        assert!(cpu.cycle().is_err());
+
+        cpu.ime = true;
+        cpu.memory_bus.write(IE_ADDRESS, 0x1F);
+        cpu.memory_bus.write(IF_ADDRESS, 0x1F);
+        for _ in 0..4 {
+            assert_eq!(cpu.cycle(), Err(CpuError::Locked { address, opcode }));
+        }
+        // Nothing was pushed or serviced and the clock kept running.
+        assert_eq!(cpu.registers.pc, 0xC001);
+        assert_eq!(cpu.registers.sp, 0xDFFE);
+        assert_eq!(cpu.memory_bus.read(IF_ADDRESS), 0x1F);
+        assert_eq!(cpu.total_cycles(), 5);
+    }
+}
@@ -0,0 +1,166 @@
+/*
+ * Interrupt dispatch, IME and HALT wake-up on a plain 64 KiB bus.
+ */
+
+mod common;
+
+use common::cpu_with_program;
+use crabbyboy::gb::bus::{Bus, FlatRam, IE_ADDRESS, IF_ADDRESS};
+use crabbyboy::gb::cpu::{StepEvent, CPU};
+use crabbyboy::gb::interrupts::InterruptKind;
+
+const NOP: u8 = 0x00;
+const INC_B: u8 = 0x04;
+const INC_A: u8 = 0x3C;
+const HALT: u8 = 0x76;
+const DI: u8 = 0xF3;
+const RETI: u8 = 0xD9;
+const EI: u8 = 0xFB;
+
+fn request(cpu: &mut CPU<FlatRam>, enable: u8, flags: u8) {
+    cpu.memory_bus.write(IE_ADDRESS, enable);
+    cpu.memory_bus.write(IF_ADDRESS, flags);
+}
+
+fn is_executed(event: StepEvent) -> bool {
+    matches!(event, StepEvent::Executed { .. })
+}
+
+#[test]
+fn ei_waits_for_the_next_instruction() {
+    let mut cpu = cpu_with_program(&[EI, NOP, NOP]);
+    request(&mut cpu, 0x01, 0x01);
+
+    assert!(is_executed(cpu.cycle().unwrap().event));
+    assert!(!cpu.ime);
+    assert!(is_executed(cpu.cycle().unwrap().event));
+    assert!(cpu.ime);
+
+    let step = cpu.cycle().unwrap();
+    assert_eq!(step.event, StepEvent::Interrupt(Some(InterruptKind::VBlank)));
+    assert_eq!(step.m_cycles, 5);
+    assert_eq!(cpu.registers.pc, 0x0040);
+    // The return address is the instruction after the NOP.
+    assert_eq!(cpu.memory_bus.read_word(cpu.registers.sp), 0xC002);
+    assert!(!cpu.ime);
+}
+
+#[test]
+fn ei_then_di_takes_no_interrupt() {
+    let mut cpu = cpu_with_program(&[EI, DI, NOP, NOP]);
+    request(&mut cpu, 0x01, 0x01);
+
+    for _ in 0..4 {
+        assert!(is_executed(cpu.cycle().unwrap().event));
+    }
+    assert!(!cpu.ime);
+    assert_eq!(cpu.registers.pc, 0xC004);
+    assert_eq!(cpu.memory_bus.read(IF_ADDRESS), 0x01);
+}
+
+#[test]
+fn interrupts_are_serviced_in_priority_order() {
+    let mut cpu = cpu_with_program(&[NOP]);
+    request(&mut cpu, 0x1F, 0x1F);
+
+    for kind in InterruptKind::ALL {
+        cpu.ime = true;
+        let step = cpu.cycle().unwrap();
+        assert_eq!(step.event, StepEvent::Interrupt(Some(kind)));
+        assert_eq!(cpu.registers.pc, kind.vector());
+    }
+}
+
+#[test]
+fn dispatch_clears_only_the_serviced_if_bit() {
+    // Timer and Joypad are requested, but only Timer is taken.
+    let mut cpu = cpu_with_program(&[NOP]);
+    request(&mut cpu, 0x1F, 0x14);
+    cpu.ime = true;
+
+    let step = cpu.cycle().unwrap();
+    assert_eq!(step.event, StepEvent::Interrupt(Some(InterruptKind::Timer)));
+    assert_eq!(cpu.memory_bus.read(IF_ADDRESS), 0x10);
+}
+
+#[test]
+fn halt_wakes_without_servicing_when_ime_is_off() {
+    let mut cpu = cpu_with_program(&[HALT, INC_A]);
+    request(&mut cpu, 0x01, 0x00);
+
+    assert!(is_executed(cpu.cycle().unwrap().event));
+    assert_eq!(cpu.cycle().unwrap().event, StepEvent::Halted);
+    cpu.memory_bus.write(IF_ADDRESS, 0x01);
+
+    // Execution carries on after HALT and the request stays pending.
+    assert!(is_executed(cpu.cycle().unwrap().event));
+    assert_eq!(cpu.registers.a, 1);
+    assert_eq!(cpu.registers.pc, 0xC002);
+    assert_eq!(cpu.memory_bus.read(IF_ADDRESS), 0x01);
+}
+
+#[test]
+fn halt_wakes_into_the_handler_when_ime_is_on() {
+    let mut cpu = cpu_with_program(&[HALT, INC_A]);
+    request(&mut cpu, 0x04, 0x00);
+    cpu.ime = true;
+
+    assert!(is_executed(cpu.cycle().unwrap().event));
+    assert_eq!(cpu.cycle().unwrap().event, StepEvent::Halted);
+    cpu.memory_bus.write(IF_ADDRESS, 0x04);
+
+    let step = cpu.cycle().unwrap();
+    assert_eq!(step.event, StepEvent::Interrupt(Some(InterruptKind::Timer)));
+    assert_eq!(cpu.registers.pc, 0x0050);
+    assert_eq!(cpu.memory_bus.read_word(cpu.registers.sp), 0xC001);
+    assert_eq!(cpu.registers.a, 0);
+    assert_eq!(cpu.memory_bus.read(IF_ADDRESS), 0x00);
+}
+
+#[test]
+fn ei_then_halt_returns_to_the_halt() {
+    // HALT still sees IME off and arms the HALT bug, but the interrupt it
+    // leads into is taken with HALT as the return address instead.
+    let mut cpu = cpu_with_program(&[EI, HALT, INC_B]);
+    cpu.memory_bus.write(0x0040, INC_A);
+    cpu.memory_bus.write(0x0041, RETI);
+    request(&mut cpu, 0x01, 0x01);
+
+    assert!(is_executed(cpu.cycle().unwrap().event));
+    assert!(is_executed(cpu.cycle().unwrap().event));
+    let step = cpu.cycle().unwrap();
+    assert_eq!(step.event, StepEvent::Interrupt(Some(InterruptKind::VBlank)));
+    assert_eq!(cpu.memory_bus.read_word(cpu.registers.sp), 0xC001);
+
+    let handler = [0x0040, 0x0041, 0xC001];
+    for address in handler {
+        match cpu.cycle().unwrap().event {
+            StepEvent::Executed { address: actual, .. } => assert_eq!(actual, address),
+            event => panic!("expected {address:#06X} to run, got {event:?}"),
+        }
+    }
+    // The handler ran once and HALT is waiting again.
+    assert_eq!(cpu.registers.a, 1);
+    assert_eq!(cpu.cycle().unwrap().event, StepEvent::Halted);
+    assert_eq!(cpu.registers.b, 0);
+}
+
+#[test]
+fn pushing_over_ie_cancels_the_dispatch() {
+    // With SP at 0x0000 the high byte of PC (0x02) lands on IE, which
+    // disables VBlank before the CPU picks what to service.
+    let mut cpu = cpu_with_program(&[NOP]);
+    cpu.registers.pc = 0x0200;
+    cpu.registers.sp = 0x0000;
+    request(&mut cpu, 0x01, 0x01);
+    cpu.ime = true;
+
+    let step = cpu.cycle().unwrap();
+    assert_eq!(step.event, StepEvent::Interrupt(None));
+    assert_eq!(step.m_cycles, 5);
+    assert_eq!(cpu.registers.pc, 0x0000);
+    assert_eq!(cpu.registers.sp, 0xFFFE);
+    assert_eq!(cpu.memory_bus.read(IE_ADDRESS), 0x02);
+    // Nothing was serviced, so the request is left alone.
+    assert_eq!(cpu.memory_bus.read(IF_ADDRESS), 0x01);
+}
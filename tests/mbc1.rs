@@ -0,0 +1,94 @@
+/*
+ * MBC1 and MBC1M banking. Every ROM bank is filled with its own number so
+ * reads show which bank is mapped.
+ */
+
+mod common;
+
+use common::numbered_rom;
+use crabbyboy::gb::cartridge::NINTENDO_LOGO;
+use crabbyboy::gb::mbc::{Mbc1, MemoryBankController};
+
+#[test]
+fn switches_rom_banks() {
+    let mut mbc = Mbc1::new(numbered_rom(128), 0, false);
+    assert_eq!(mbc.read_rom(0x0000), 0);
+    assert_eq!(mbc.read_rom(0x4000), 1);
+    mbc.write_rom(0x2000, 0x05);
+    assert_eq!(mbc.read_rom(0x7FFF), 5);
+    // Only the low 5 bits are wired.
+    mbc.write_rom(0x2000, 0xE7);
+    assert_eq!(mbc.read_rom(0x4000), 7);
+    mbc.write_rom(0x4000, 0x02);
+    assert_eq!(mbc.read_rom(0x4000), 0x47);
+    // Mode 0 keeps bank 0 at 0x0000, mode 1 applies BANK2 there too.
+    assert_eq!(mbc.read_rom(0x0000), 0);
+    mbc.write_rom(0x6000, 0x01);
+    assert_eq!(mbc.read_rom(0x0000), 0x40);
+}
+
+#[test]
+fn bank_zero_maps_to_one() {
+    let mut mbc = Mbc1::new(numbered_rom(128), 0, false);
+    mbc.write_rom(0x2000, 0x00);
+    assert_eq!(mbc.read_rom(0x4000), 1);
+    // The check is on BANK1 only, so 0x20 turns into 0x21.
+    mbc.write_rom(0x4000, 0x01);
+    mbc.write_rom(0x2000, 0x20);
+    assert_eq!(mbc.read_rom(0x4000), 0x21);
+}
+
+#[test]
+fn bank_numbers_wrap_to_rom_size() {
+    let mut mbc = Mbc1::new(numbered_rom(8), 0, false);
+    mbc.write_rom(0x2000, 0x0B);
+    assert_eq!(mbc.read_rom(0x4000), 3);
+}
+
+#[test]
+fn ram_needs_enabling() {
+    let mut mbc = Mbc1::new(numbered_rom(4), 0x8000, false);
+    mbc.write_ram(0xA000, 0x42);
+    assert_eq!(mbc.read_ram(0xA000), 0xFF);
+
+    mbc.write_rom(0x0000, 0x0A);
+    mbc.write_ram(0xA000, 0x42);
+    assert_eq!(mbc.read_ram(0xA000), 0x42);
+    mbc.write_rom(0x0000, 0x00);
+    assert_eq!(mbc.read_ram(0xA000), 0xFF);
+}
+
+#[test]
+fn ram_banks_only_switch_in_mode_one() {
+    let mut mbc = Mbc1::new(numbered_rom(4), 0x8000, false);
+    mbc.write_rom(0x0000, 0x0A);
+    mbc.write_ram(0xA000, 0x11);
+    mbc.write_rom(0x4000, 0x02);
+    assert_eq!(mbc.read_ram(0xA000), 0x11);
+    mbc.write_rom(0x6000, 0x01);
+    assert_eq!(mbc.read_ram(0xA000), 0x00);
+    mbc.write_ram(0xA000, 0x22);
+    mbc.write_rom(0x4000, 0x00);
+    assert_eq!(mbc.read_ram(0xA000), 0x11);
+}
+
+#[test]
+fn multicart_uses_4_bit_bank1() {
+    let mut rom = numbered_rom(64);
+    assert!(!Mbc1::detect_multicart(&rom));
+    for game in 0..4 {
+        let logo = game * 0x40000 + 0x0104;
+        rom[logo..logo + NINTENDO_LOGO.len()].copy_from_slice(&NINTENDO_LOGO);
+    }
+    assert!(Mbc1::detect_multicart(&rom));
+
+    let mut mbc = Mbc1::new(rom, 0, true);
+    mbc.write_rom(0x4000, 0x01);
+    mbc.write_rom(0x2000, 0x13);
+    assert_eq!(mbc.read_rom(0x4000), 0x13);
+    mbc.write_rom(0x6000, 0x01);
+    assert_eq!(mbc.read_rom(0x0000), 0x10);
+    mbc.write_rom(0x4000, 0x03);
+    assert_eq!(mbc.read_rom(0x0000), 0x30);
+    assert_eq!(mbc.read_rom(0x4000), 0x33);
+}
@@ -0,0 +1,42 @@
+/*
+ * MBC2 banking and its built-in half-byte RAM.
+ */
+
+mod common;
+
+use common::numbered_rom;
+use crabbyboy::gb::mbc::{Mbc2, MemoryBankController};
+
+#[test]
+fn address_bit_8_selects_the_register() {
+    let mut mbc = Mbc2::new(numbered_rom(16));
+    assert_eq!(mbc.read_rom(0x4000), 1);
+    // Bit 8 clear is RAM enable, so the ROM bank stays put.
+    mbc.write_rom(0x2000, 0x05);
+    assert_eq!(mbc.read_rom(0x4000), 1);
+    mbc.write_rom(0x2100, 0x05);
+    assert_eq!(mbc.read_rom(0x4000), 5);
+    mbc.write_rom(0x3FFF, 0xFF);
+    assert_eq!(mbc.read_rom(0x7FFF), 15);
+    mbc.write_rom(0x0100, 0x10);
+    assert_eq!(mbc.read_rom(0x4000), 1);
+    assert_eq!(mbc.read_rom(0x0000), 0);
+}
+
+#[test]
+fn ram_is_512_nibbles_echoed_across_the_range() {
+    let mut mbc = Mbc2::new(numbered_rom(16));
+    mbc.write_ram(0xA000, 0x0C);
+    assert_eq!(mbc.read_ram(0xA000), 0xFF);
+
+    mbc.write_rom(0x0000, 0x0A);
+    mbc.write_ram(0xA000, 0xAC);
+    assert_eq!(mbc.read_ram(0xA000), 0xFC);
+    assert_eq!(mbc.read_ram(0xA200), 0xFC);
+    assert_eq!(mbc.read_ram(0xBE00), 0xFC);
+    mbc.write_ram(0xBFFF, 0x03);
+    assert_eq!(mbc.read_ram(0xA1FF), 0xF3);
+
+    mbc.write_rom(0x0000, 0x00);
+    assert_eq!(mbc.read_ram(0xA000), 0xFF);
+}
@@ -0,0 +1,139 @@
+/*
+ * MBC3 banking and its real-time clock, driven by a clock the tests move
+ * by hand.
+ */
+
+mod common;
+
+use common::{numbered_rom, TestClock};
+use crabbyboy::gb::mbc::{Mbc3, MemoryBankController};
+
+const SECONDS: u8 = 0x08;
+const MINUTES: u8 = 0x09;
+const HOURS: u8 = 0x0A;
+const DAYS_LOW: u8 = 0x0B;
+const DAYS_HIGH: u8 = 0x0C;
+
+fn mbc_with_clock(clock: &TestClock) -> Mbc3 {
+    let mut mbc = Mbc3::new(numbered_rom(4), 0x8000, Some(Box::new(clock.clone())));
+    mbc.write_rom(0x0000, 0x0A);
+    mbc
+}
+
+fn latch(mbc: &mut Mbc3) {
+    mbc.write_rom(0x6000, 0x00);
+    mbc.write_rom(0x6000, 0x01);
+}
+
+fn read_rtc(mbc: &mut Mbc3, register: u8) -> u8 {
+    mbc.write_rom(0x4000, register);
+    mbc.read_ram(0xA000)
+}
+
+fn write_rtc(mbc: &mut Mbc3, register: u8, val: u8) {
+    mbc.write_rom(0x4000, register);
+    mbc.write_ram(0xA000, val);
+}
+
+#[test]
+fn switches_rom_and_ram_banks() {
+    let mut mbc = Mbc3::new(numbered_rom(128), 0x8000, None);
+    mbc.write_rom(0x2000, 0x00);
+    assert_eq!(mbc.read_rom(0x4000), 1);
+    mbc.write_rom(0x2000, 0x7F);
+    assert_eq!(mbc.read_rom(0x4000), 0x7F);
+    assert_eq!(mbc.read_rom(0x0000), 0);
+
+    mbc.write_rom(0x0000, 0x0A);
+    mbc.write_rom(0x4000, 0x03);
+    mbc.write_ram(0xA000, 0x33);
+    mbc.write_rom(0x4000, 0x01);
+    assert_eq!(mbc.read_ram(0xA000), 0x00);
+    mbc.write_rom(0x4000, 0x03);
+    assert_eq!(mbc.read_ram(0xA000), 0x33);
+    // No clock on this board.
+    assert_eq!(read_rtc(&mut mbc, SECONDS), 0xFF);
+}
+
+#[test]
+fn clock_only_shows_time_once_latched() {
+    let clock = TestClock::default();
+    let mut mbc = mbc_with_clock(&clock);
+    clock.advance(3 * 3600 + 2 * 60 + 1);
+    assert_eq!(read_rtc(&mut mbc, SECONDS), 0);
+
+    latch(&mut mbc);
+    assert_eq!(read_rtc(&mut mbc, SECONDS), 1);
+    assert_eq!(read_rtc(&mut mbc, MINUTES), 2);
+    assert_eq!(read_rtc(&mut mbc, HOURS), 3);
+
+    // Writing 0x01 again without 0x00 first doesn't latch.
+    clock.advance(10);
+    mbc.write_rom(0x6000, 0x01);
+    assert_eq!(read_rtc(&mut mbc, SECONDS), 1);
+}
+
+#[test]
+fn days_carry_past_511() {
+    let clock = TestClock::default();
+    let mut mbc = mbc_with_clock(&clock);
+    clock.advance(300 * 86400);
+    latch(&mut mbc);
+    assert_eq!(read_rtc(&mut mbc, DAYS_LOW), (300 & 0xFF) as u8);
+    assert_eq!(read_rtc(&mut mbc, DAYS_HIGH), 0x01);
+
+    clock.advance(212 * 86400 + 5);
+    latch(&mut mbc);
+    assert_eq!(read_rtc(&mut mbc, DAYS_LOW), 0);
+    assert_eq!(read_rtc(&mut mbc, DAYS_HIGH), 0x80);
+    assert_eq!(read_rtc(&mut mbc, SECONDS), 5);
+}
+
+#[test]
+fn halt_stops_the_clock() {
+    let clock = TestClock::default();
+    let mut mbc = mbc_with_clock(&clock);
+    clock.advance(10);
+    write_rtc(&mut mbc, DAYS_HIGH, 0x40);
+    clock.advance(1000);
+    latch(&mut mbc);
+    assert_eq!(read_rtc(&mut mbc, SECONDS), 10);
+
+    write_rtc(&mut mbc, DAYS_HIGH, 0x00);
+    clock.advance(5);
+    latch(&mut mbc);
+    assert_eq!(read_rtc(&mut mbc, SECONDS), 15);
+}
+
+#[test]
+fn out_of_range_values_wrap_without_carrying() {
+    let clock = TestClock::default();
+    let mut mbc = mbc_with_clock(&clock);
+    write_rtc(&mut mbc, SECONDS, 62);
+    clock.advance(3);
+    latch(&mut mbc);
+    assert_eq!(read_rtc(&mut mbc, SECONDS), 1);
+    assert_eq!(read_rtc(&mut mbc, MINUTES), 0);
+}
+
+#[test]
+fn reloading_a_save_catches_up_on_elapsed_time() {
+    let clock = TestClock::default();
+    clock.advance(1_700_000_000);
+    let mut mbc = mbc_with_clock(&clock);
+    mbc.write_rom(0x4000, 0x00);
+    mbc.write_ram(0xA000, 0x42);
+    write_rtc(&mut mbc, MINUTES, 30);
+    let save = mbc.save_data();
+    assert_eq!(save.len(), 0x8000 + 48);
+
+    // Two hours later the game is started again.
+    clock.advance(2 * 3600);
+    let mut mbc = mbc_with_clock(&clock);
+    mbc.load_save_data(&save);
+    mbc.write_rom(0x4000, 0x00);
+    assert_eq!(mbc.read_ram(0xA000), 0x42);
+    latch(&mut mbc);
+    assert_eq!(read_rtc(&mut mbc, MINUTES), 30);
+    assert_eq!(read_rtc(&mut mbc, HOURS), 2);
+}
@@ -0,0 +1,65 @@
+/*
+ * MBC5 banking and the rumble motor.
+ */
+
+mod common;
+
+use common::wide_numbered_rom;
+use crabbyboy::gb::mbc::{Mbc5, MemoryBankController};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn bank_at(mbc: &Mbc5, address: u16) -> u16 {
+    (mbc.read_rom(address + 1) as u16) << 8 | mbc.read_rom(address) as u16
+}
+
+#[test]
+fn rom_bank_is_9_bits_and_can_be_0() {
+    let mut mbc = Mbc5::new(wide_numbered_rom(512), 0, false);
+    assert_eq!(bank_at(&mbc, 0x4000), 1);
+    mbc.write_rom(0x2000, 0x00);
+    assert_eq!(bank_at(&mbc, 0x4000), 0);
+    mbc.write_rom(0x2FFF, 0x34);
+    mbc.write_rom(0x3000, 0x01);
+    assert_eq!(bank_at(&mbc, 0x4000), 0x134);
+    assert_eq!(bank_at(&mbc, 0x0000), 0);
+    mbc.write_rom(0x3FFF, 0xFE);
+    assert_eq!(bank_at(&mbc, 0x7FFE), 0x034);
+}
+
+#[test]
+fn ram_has_16_banks() {
+    let mut mbc = Mbc5::new(wide_numbered_rom(2), 0x20000, false);
+    // Only exactly 0x0A enables RAM.
+    mbc.write_rom(0x0000, 0x1A);
+    mbc.write_ram(0xA000, 0x42);
+    assert_eq!(mbc.read_ram(0xA000), 0xFF);
+
+    mbc.write_rom(0x0000, 0x0A);
+    mbc.write_rom(0x4000, 0x0F);
+    mbc.write_ram(0xBFFF, 0x42);
+    mbc.write_rom(0x4000, 0x07);
+    assert_eq!(mbc.read_ram(0xBFFF), 0x00);
+    mbc.write_rom(0x4000, 0x0F);
+    assert_eq!(mbc.read_ram(0xBFFF), 0x42);
+}
+
+#[test]
+fn bit_3_drives_the_rumble_motor() {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let mut mbc = Mbc5::new(wide_numbered_rom(2), 0x8000, true);
+    let log = events.clone();
+    mbc.set_rumble_listener(Box::new(move |on| log.borrow_mut().push(on)));
+
+    mbc.write_rom(0x0000, 0x0A);
+    mbc.write_rom(0x4000, 0x0B);
+    assert_eq!(mbc.rumble(), Some(true));
+    mbc.write_ram(0xA000, 0x42);
+    mbc.write_rom(0x4000, 0x0B);
+    mbc.write_rom(0x4000, 0x03);
+    assert_eq!(mbc.read_ram(0xA000), 0x42);
+    assert_eq!(*events.borrow(), [true, false]);
+
+    let mbc = Mbc5::new(wide_numbered_rom(2), 0, false);
+    assert_eq!(mbc.rumble(), None);
+}
@@ -0,0 +1,111 @@
+/*
+ * MBC7's accelerometer and its bit-banged 93LC56 EEPROM.
+ */
+
+use crabbyboy::gb::mbc::{Mbc7, MemoryBankController};
+
+const CS: u8 = 0x80;
+const CLK: u8 = 0x40;
+const DI: u8 = 0x02;
+
+fn enabled_mbc() -> Mbc7 {
+    let mut mbc = Mbc7::new(vec![0; 0x8000]);
+    mbc.write_rom(0x0000, 0x0A);
+    mbc.write_rom(0x4000, 0x40);
+    mbc
+}
+
+fn read_u16(mbc: &Mbc7, low: u16) -> u16 {
+    (mbc.read_ram(low + 0x10) as u16) << 8 | mbc.read_ram(low) as u16
+}
+
+/* Clocks one bit into the EEPROM and returns DO after the rising edge. */
+fn clock_bit(mbc: &mut Mbc7, bit: bool) -> bool {
+    let di = if bit { DI } else { 0 };
+    mbc.write_ram(0xA080, CS | di);
+    mbc.write_ram(0xA080, CS | CLK | di);
+    mbc.read_ram(0xA080) & 0x01 != 0
+}
+
+fn send(mbc: &mut Mbc7, bits: u32, count: u32) {
+    for n in (0..count).rev() {
+        clock_bit(mbc, bits >> n & 1 != 0);
+    }
+}
+
+/* Start bit, 2-bit opcode and 8-bit address, then an optional data word. */
+fn command(mbc: &mut Mbc7, opcode: u32, address: u32, data: Option<u16>) {
+    mbc.write_ram(0xA080, 0x00);
+    send(mbc, 1 << 10 | opcode << 8 | address, 11);
+    if let Some(data) = data {
+        send(mbc, data as u32, 16);
+    }
+    mbc.write_ram(0xA080, 0x00);
+}
+
+fn read_word(mbc: &mut Mbc7, address: u32) -> u16 {
+    mbc.write_ram(0xA080, 0x00);
+    send(mbc, 0b110 << 8 | address, 11);
+    assert_eq!(mbc.read_ram(0xA080) & 0x01, 0, "missing dummy bit");
+    let word = (0..16).fold(0, |word, _| word << 1 | clock_bit(mbc, false) as u16);
+    mbc.write_ram(0xA080, 0x00);
+    word
+}
+
+#[test]
+fn accelerometer_needs_both_enables_and_a_latch() {
+    let mut mbc = Mbc7::new(vec![0; 0x8000]);
+    mbc.write_rom(0x0000, 0x0A);
+    assert_eq!(mbc.read_ram(0xA020), 0xFF);
+    mbc.write_rom(0x4000, 0x40);
+    assert_eq!(read_u16(&mbc, 0xA020), 0x8000);
+
+    mbc.set_tilt(1.0, -0.5);
+    // 0xAA alone doesn't latch, it needs 0x55 first.
+    mbc.write_ram(0xA010, 0xAA);
+    assert_eq!(read_u16(&mbc, 0xA020), 0x8000);
+    mbc.write_ram(0xA000, 0x55);
+    mbc.write_ram(0xA010, 0xAA);
+    assert_eq!(read_u16(&mbc, 0xA020), 0x81D0 + 0x70);
+    assert_eq!(read_u16(&mbc, 0xA040), 0x81D0 - 0x38);
+
+    // Latched values stay put until the next latch.
+    mbc.set_tilt(0.0, 0.0);
+    assert_eq!(read_u16(&mbc, 0xA020), 0x81D0 + 0x70);
+    assert_eq!(mbc.read_ram(0xA060), 0x00);
+}
+
+#[test]
+fn eeprom_writes_need_write_enable() {
+    let mut mbc = enabled_mbc();
+    command(&mut mbc, 0b01, 0x05, Some(0x1234));
+    assert_eq!(read_word(&mut mbc, 0x05), 0xFFFF);
+
+    // EWEN is opcode 00 with 11 in the top address bits.
+    command(&mut mbc, 0b00, 0xC0, None);
+    command(&mut mbc, 0b01, 0x05, Some(0x1234));
+    assert_eq!(read_word(&mut mbc, 0x05), 0x1234);
+
+    command(&mut mbc, 0b11, 0x05, None);
+    assert_eq!(read_word(&mut mbc, 0x05), 0xFFFF);
+
+    // WRAL, then EWDS stops ERAL from doing anything.
+    command(&mut mbc, 0b00, 0x40, Some(0xBEEF));
+    command(&mut mbc, 0b00, 0x00, None);
+    command(&mut mbc, 0b00, 0x80, None);
+    assert_eq!(read_word(&mut mbc, 0x7F), 0xBEEF);
+}
+
+#[test]
+fn eeprom_is_saved_like_ram() {
+    let mut mbc = enabled_mbc();
+    command(&mut mbc, 0b00, 0xC0, None);
+    command(&mut mbc, 0b01, 0x01, Some(0xA55A));
+    let save = mbc.save_data();
+    assert_eq!(save.len(), 256);
+    assert_eq!(save[2..4], [0x5A, 0xA5]);
+
+    let mut mbc = enabled_mbc();
+    mbc.load_save_data(&save);
+    assert_eq!(read_word(&mut mbc, 0x01), 0xA55A);
+}
@@ -0,0 +1,107 @@
+/*
+ * The Game Boy memory map as seen through the MemoryManagementUnit.
+ */
+
+use crabbyboy::gb::boot::{BootRom, BootRomError};
+use crabbyboy::gb::mmu::MemoryManagementUnit as MMU;
+
+#[test]
+fn rom_is_read_only() {
+    let mut mmu = MMU::new();
+    mmu.load_rom([0x31, 0xFE, 0xFF].iter());
+    mmu.set_byte(0x0001, 0x00);
+    assert_eq!(mmu.read_byte(0x0001), 0xFE);
+    // Past the end of the ROM the bus floats high.
+    assert_eq!(mmu.read_byte(0x7FFF), 0xFF);
+}
+
+#[test]
+fn no_cartridge_ram_reads_open_bus() {
+    let mut mmu = MMU::new();
+    mmu.set_byte(0xA000, 0x42);
+    assert_eq!(mmu.read_byte(0xA000), 0xFF);
+}
+
+#[test]
+fn echo_ram_mirrors_wram() {
+    let mut mmu = MMU::new();
+    mmu.set_byte(0xC123, 0x42);
+    assert_eq!(mmu.read_byte(0xE123), 0x42);
+    mmu.set_byte(0xFDFF, 0x24);
+    assert_eq!(mmu.read_byte(0xDDFF), 0x24);
+    // 0xDE00-0xDFFF has no mirror, OAM sits where it would be.
+    mmu.set_byte(0xDE00, 0x99);
+    assert_eq!(mmu.read_byte(0xFE00), 0x00);
+}
+
+#[test]
+fn unusable_area_ignores_writes() {
+    let mut mmu = MMU::new();
+    for address in 0xFEA0..=0xFEFF {
+        mmu.set_byte(address, 0xAA);
+        assert_eq!(mmu.read_byte(address), 0x00);
+    }
+}
+
+#[test]
+fn regions_are_separate() {
+    let mut mmu = MMU::new();
+    let regions = [0x8000, 0x9FFF, 0xC000, 0xDFFF, 0xFE00, 0xFE9F, 0xFF80, 0xFFFE];
+    for (n, address) in regions.into_iter().enumerate() {
+        mmu.set_byte(address, n as u8 + 1);
+    }
+    for (n, address) in regions.into_iter().enumerate() {
+        assert_eq!(mmu.read_byte(address), n as u8 + 1, "{address:#06X}");
+    }
+}
+
+#[test]
+fn io_goes_to_devices() {
+    let mut mmu = MMU::new();
+    for _ in 0..64 {
+        mmu.tick();
+    }
+    assert_eq!(mmu.read_byte(0xFF04), 0x01);
+    mmu.set_byte(0xFF04, 0x55);
+    assert_eq!(mmu.read_byte(0xFF04), 0x00);
+
+    mmu.set_byte(0xFF0F, 0x04);
+    assert_eq!(mmu.interrupts.read_flag(), 0xE4);
+    mmu.set_byte(0xFFFF, 0x05);
+    assert_eq!(mmu.interrupts.pending(), 0x04);
+}
+
+#[test]
+fn boot_rom_shadows_cartridge_until_ff50() {
+    let mut mmu = MMU::new();
+    mmu.load_rom(vec![0xC3; 0x8000].iter());
+    mmu.load_boot_rom(BootRom::new(vec![0x31; 0x100]).unwrap());
+    assert_eq!(mmu.read_byte(0x0000), 0x31);
+    assert_eq!(mmu.read_byte(0x00FF), 0x31);
+    assert_eq!(mmu.read_byte(0x0100), 0xC3);
+
+    // Bit 0 has to be set to unmap it.
+    mmu.set_byte(0xFF50, 0x00);
+    assert_eq!(mmu.read_byte(0x0000), 0x31);
+    mmu.set_byte(0xFF50, 0x01);
+    assert_eq!(mmu.read_byte(0x0000), 0xC3);
+    assert!(!mmu.boot_rom_mapped());
+}
+
+#[test]
+fn cgb_boot_rom_leaves_header_visible() {
+    let mut mmu = MMU::new();
+    mmu.load_rom(vec![0xC3; 0x8000].iter());
+    mmu.load_boot_rom(BootRom::new(vec![0x31; 0x900]).unwrap());
+    assert_eq!(mmu.read_byte(0x00FF), 0x31);
+    assert_eq!(mmu.read_byte(0x0100), 0xC3);
+    assert_eq!(mmu.read_byte(0x01FF), 0xC3);
+    assert_eq!(mmu.read_byte(0x0200), 0x31);
+    assert_eq!(mmu.read_byte(0x08FF), 0x31);
+    assert_eq!(mmu.read_byte(0x0900), 0xC3);
+}
+
+#[test]
+fn boot_rom_size_is_checked() {
+    assert_eq!(BootRom::new(vec![0; 0x200]).unwrap_err(), BootRomError::Size(0x200));
+}
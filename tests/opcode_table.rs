@@ -0,0 +1,89 @@
+/*
+ * Runs every opcode once and checks its timing and length against the
+ * metadata in gb::opcodes, so the executor and the table cannot drift apart.
+ */
+
+mod common;
+
+use common::cpu_with_program;
+use crabbyboy::gb::bus::{Bus, FlatRam};
+use crabbyboy::gb::cpu::CPU;
+use crabbyboy::gb::opcodes::Opcode;
+
+/* Sets `opcode` up at 0xC000 so that every jump, call and return lands on
+ * the byte right after the instruction, where it would have fallen through
+ * to. Only RST ignores its operands and goes to its fixed vector.
+ */
+fn cpu_for(opcode: u8, prefixed: bool, flags: u8) -> CPU<FlatRam> {
+    let info = Opcode::lookup(opcode, prefixed);
+    let next = 0xC000 + info.length as u16;
+    let program = match (prefixed, info.length) {
+        (true, _) => vec![0xCB, opcode],
+        // JR's offset is relative to the next instruction.
+        (false, 2) => vec![opcode, 0x00],
+        (false, 3) => vec![opcode, (next & 0xFF) as u8, (next >> 8) as u8],
+        _ => vec![opcode],
+    };
+    let mut cpu = cpu_with_program(&program);
+    cpu.registers.f = flags.into();
+    // JP HL and the returns also come back to the next instruction.
+    cpu.registers.set_hl(next);
+    cpu.memory_bus.write_word(cpu.registers.sp, next);
+    cpu
+}
+
+/* Runs `opcode` with all flags reset and then all set, which takes each
+ * conditional branch one way and then the other. Returns the M-cycles each
+ * run took.
+ */
+fn run(opcode: u8, prefixed: bool) -> Vec<u8> {
+    let info = Opcode::lookup(opcode, prefixed);
+    let name = info.mnemonic;
+    let mut taken = Vec::new();
+    for flags in [0x00, 0xF0] {
+        let mut cpu = cpu_for(opcode, prefixed, flags);
+        let step = cpu.cycle().unwrap_or_else(|err| panic!("{name}: {err}"));
+        taken.push(step.m_cycles);
+
+        let pc = cpu.registers.pc;
+        if name.starts_with("RST") {
+            assert_eq!(pc, (opcode & 0x38) as u16, "{name}");
+        } else {
+            assert_eq!(pc.wrapping_sub(0xC000), info.length as u16, "{name} length");
+        }
+    }
+    taken
+}
+
+#[test]
+fn base_opcodes_match_the_table() {
+    for opcode in 0..=0xFF {
+        let info = Opcode::lookup(opcode, false);
+        // The illegal opcodes lock up and 0xCB is only a prefix.
+        if info.instruction.is_err() {
+            continue;
+        }
+        let cycles = run(opcode, false);
+        match info.branch_cycles {
+            Some(branch) => {
+                let mut expected = vec![info.cycles, branch];
+                let mut cycles = cycles;
+                expected.sort();
+                cycles.sort();
+                assert_eq!(cycles, expected, "{} M-cycles", info.mnemonic);
+            }
+            None => {
+                assert_eq!(cycles, vec![info.cycles; 2], "{} M-cycles", info.mnemonic);
+            }
+        }
+    }
+}
+
+#[test]
+fn prefixed_opcodes_match_the_table() {
+    for opcode in 0..=0xFF {
+        let info = Opcode::lookup(opcode, true);
+        let cycles = run(opcode, true);
+        assert_eq!(cycles, vec![info.cycles; 2], "{} M-cycles", info.mnemonic);
+    }
+}
@@ -0,0 +1,75 @@
+/*
+ * Starting at 0x0100 in the state each model's boot ROM leaves behind.
+ */
+
+use crabbyboy::gb::boot::BootRom;
+use crabbyboy::gb::cpu::CPU;
+use crabbyboy::gb::model::Model;
+
+fn cpu_with_header_checksum(checksum: u8) -> CPU {
+    let mut rom = vec![0x00; 0x8000];
+    rom[0x014D] = checksum;
+    let mut cpu = CPU::new();
+    cpu.memory_bus.load_rom(rom.iter());
+    cpu
+}
+
+fn registers(cpu: &CPU) -> [u16; 6] {
+    let r = &cpu.registers;
+    [r.af(), r.bc(), r.de(), r.hl(), r.sp, r.pc]
+}
+
+#[test]
+fn dmg_flags_follow_header_checksum() {
+    let mut cpu = cpu_with_header_checksum(0x66);
+    cpu.skip_boot(Model::Dmg);
+    assert_eq!(registers(&cpu), [0x01B0, 0x0013, 0x00D8, 0x014D, 0xFFFE, 0x0100]);
+
+    let mut cpu = cpu_with_header_checksum(0x00);
+    cpu.skip_boot(Model::Dmg);
+    assert_eq!(cpu.registers.af(), 0x0180);
+}
+
+#[test]
+fn models_are_told_apart_by_registers() {
+    let expected = [
+        (Model::Dmg0, [0x0100, 0xFF13, 0x00C1, 0x8403]),
+        (Model::Mgb, [0xFFB0, 0x0013, 0x00D8, 0x014D]),
+        (Model::Sgb, [0x0100, 0x0014, 0x0000, 0xC060]),
+        (Model::Sgb2, [0xFF00, 0x0014, 0x0000, 0xC060]),
+        (Model::Cgb, [0x1180, 0x0000, 0xFF56, 0x000D]),
+        (Model::Agb, [0x1100, 0x0100, 0xFF56, 0x000D]),
+    ];
+    for (model, [af, bc, de, hl]) in expected {
+        let mut cpu = cpu_with_header_checksum(0x66);
+        cpu.skip_boot(model);
+        assert_eq!(registers(&cpu), [af, bc, de, hl, 0xFFFE, 0x0100], "{model:?}");
+    }
+}
+
+#[test]
+fn io_and_div_match_the_boot_rom() {
+    let mut cpu = cpu_with_header_checksum(0x66);
+    cpu.skip_boot(Model::Dmg);
+    let bus = &cpu.memory_bus;
+    assert_eq!(bus.read_byte(0xFF04), 0xAB);
+    assert_eq!(bus.read_byte(0xFF0F), 0xE1);
+    assert_eq!(bus.read_byte(0xFFFF), 0x00);
+    assert_eq!(bus.read_byte(0xFF40), 0x91);
+    assert_eq!(bus.read_byte(0xFF47), 0xFC);
+    assert_eq!(bus.read_byte(0xFF26), 0xF1);
+
+    let mut cpu = cpu_with_header_checksum(0x66);
+    cpu.skip_boot(Model::Cgb);
+    assert_eq!(cpu.memory_bus.read_byte(0xFF4D), 0x7E);
+    assert_eq!(cpu.memory_bus.read_byte(0xFF02), 0x7F);
+}
+
+#[test]
+fn skipping_boot_unmaps_the_boot_rom() {
+    let mut cpu = cpu_with_header_checksum(0x66);
+    cpu.memory_bus.load_boot_rom(BootRom::new(vec![0x31; 0x100]).unwrap());
+    cpu.skip_boot(Model::Dmg);
+    assert!(!cpu.memory_bus.boot_rom_mapped());
+    assert_eq!(cpu.memory_bus.read_byte(0x0000), 0x00);
+}
@@ -0,0 +1,222 @@
+/*
+ * Conformance harness for the SingleStepTests sm83 vectors
+ * (https://github.com/SingleStepTests/sm83). Each JSON file holds a batch of
+ * cases for one opcode, named like `4a.json` or `cb 4a.json`: the registers
+ * and RAM before and after the instruction, plus every M-cycle of bus
+ * activity in between. The CPU runs on a flat RAM bus that records each
+ * access, so the timing of every read and write is checked as well.
+ *
+ * The full suite is too big to keep in the repo. Point SM83_TESTS_DIR at a
+ * checkout of its `v1` directory to run it; otherwise only the handful of
+ * hand-written cases in tests/sm83/fixtures are checked.
+ */
+
+use crabbyboy::gb::bus::{Bus, FlatRam};
+use crabbyboy::gb::cpu::CPU;
+use crabbyboy::gb::interrupts::InterruptKind;
+use crabbyboy::gb::registers::FlagsRegisters;
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+const FIXTURES_DIR: &str = "tests/sm83/fixtures";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Access {
+    Read(u16, u8),
+    Write(u16, u8),
+}
+
+/* Flat RAM that logs the access made in each M-cycle, if any. */
+#[derive(Default)]
+struct RecordingBus {
+    ram: FlatRam,
+    current: Option<Access>,
+    cycles: Vec<Option<Access>>,
+}
+
+impl Bus for RecordingBus {
+    fn read(&mut self, address: u16) -> u8 {
+        let val = self.ram.read(address);
+        self.current = Some(Access::Read(address, val));
+        val
+    }
+
+    fn write(&mut self, address: u16, val: u8) {
+        self.ram.write(address, val);
+        self.current = Some(Access::Write(address, val));
+    }
+
+    fn tick(&mut self) {
+        let access = self.current.take();
+        self.cycles.push(access);
+    }
+
+    // Interrupt checks happen between bus cycles, so keep them off the log.
+    fn pending_interrupts(&mut self) -> u8 {
+        self.ram.pending_interrupts()
+    }
+
+    fn acknowledge_interrupt(&mut self, kind: InterruptKind) {
+        self.ram.acknowledge_interrupt(kind);
+    }
+}
+
+#[test]
+fn sm83_fixtures() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(FIXTURES_DIR);
+    assert_all_pass(&dir);
+}
+
+#[test]
+fn sm83_single_step_tests() {
+    let Some(dir) = env::var_os("SM83_TESTS_DIR") else {
+        println!("SM83_TESTS_DIR is not set, skipping the full sm83 suite");
+        return;
+    };
+    assert_all_pass(Path::new(&dir));
+}
+
+/* Runs every file in `dir`, prints a pass/fail line per opcode and fails
+ * the test with the first mismatch of each failing opcode.
+ */
+fn assert_all_pass(dir: &Path) {
+    let mut results: BTreeMap<String, (usize, Vec<String>)> = BTreeMap::new();
+    for path in test_files(dir) {
+        let opcode = path.file_stem().unwrap().to_string_lossy().into_owned();
+        results.insert(opcode, run_file(&path));
+    }
+    assert!(!results.is_empty(), "no test files found in {}", dir.display());
+
+    let mut failures = Vec::new();
+    for (opcode, (passed, failed)) in &results {
+        let total = passed + failed.len();
+        let status = if failed.is_empty() { "pass" } else { "FAIL" };
+        println!("{opcode:>6}: {status} {passed}/{total}");
+        if let Some(first) = failed.first() {
+            failures.push(format!("{opcode}: {first}"));
+        }
+    }
+    println!("{} of {} opcodes pass", results.len() - failures.len(), results.len());
+    assert!(failures.is_empty(), "failing opcodes:\n{}", failures.join("\n"));
+}
+
+fn test_files(dir: &Path) -> Vec<PathBuf> {
+    let entries = fs::read_dir(dir).unwrap_or_else(|err| panic!("{}: {err}", dir.display()));
+    let mut files: Vec<PathBuf> = entries
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+    files
+}
+
+/* Returns the number of passing cases and a description of each failure. */
+fn run_file(path: &Path) -> (usize, Vec<String>) {
+    let text = fs::read_to_string(path).unwrap();
+    let cases: Vec<Value> = serde_json::from_str(&text).unwrap();
+
+    let mut passed = 0;
+    let mut failed = Vec::new();
+    for case in &cases {
+        match run_case(case) {
+            Ok(()) => passed += 1,
+            Err(err) => failed.push(format!("{} - {err}", case["name"])),
+        }
+    }
+    (passed, failed)
+}
+
+fn run_case(case: &Value) -> Result<(), String> {
+    let initial = &case["initial"];
+    let expected = &case["final"];
+
+    let mut cpu = CPU::with_bus(RecordingBus::default());
+    cpu.registers.pc = num(&initial["pc"]) as u16;
+    cpu.registers.sp = num(&initial["sp"]) as u16;
+    cpu.registers.a = num(&initial["a"]) as u8;
+    cpu.registers.b = num(&initial["b"]) as u8;
+    cpu.registers.c = num(&initial["c"]) as u8;
+    cpu.registers.d = num(&initial["d"]) as u8;
+    cpu.registers.e = num(&initial["e"]) as u8;
+    cpu.registers.f = FlagsRegisters::from(num(&initial["f"]) as u8);
+    cpu.registers.h = num(&initial["h"]) as u8;
+    cpu.registers.l = num(&initial["l"]) as u8;
+    cpu.ime = num(&initial["ime"]) != 0;
+    for (address, val) in ram(initial) {
+        cpu.memory_bus.ram.write(address, val);
+    }
+
+    let step = cpu.cycle().map_err(|err| err.to_string())?;
+
+    let registers = [
+        ("pc", cpu.registers.pc as u64),
+        ("sp", cpu.registers.sp as u64),
+        ("a", cpu.registers.a as u64),
+        ("b", cpu.registers.b as u64),
+        ("c", cpu.registers.c as u64),
+        ("d", cpu.registers.d as u64),
+        ("e", cpu.registers.e as u64),
+        ("f", u8::from(cpu.registers.f) as u64),
+        ("h", cpu.registers.h as u64),
+        ("l", cpu.registers.l as u64),
+        ("ime", cpu.ime as u64),
+    ];
+    for (name, actual) in registers {
+        let want = num(&expected[name]);
+        if actual != want {
+            return Err(format!("{name} is {actual:#X}, expected {want:#X}"));
+        }
+    }
+
+    for (address, want) in ram(expected) {
+        let actual = cpu.memory_bus.ram.read(address);
+        if actual != want {
+            return Err(format!("[{address:#06X}] is {actual:#04X}, expected {want:#04X}"));
+        }
+    }
+
+    let cycles = case["cycles"].as_array().cloned().unwrap_or_default();
+    if step.m_cycles as usize != cycles.len() {
+        return Err(format!("took {} M-cycles, expected {}", step.m_cycles, cycles.len()));
+    }
+    for (n, (cycle, actual)) in cycles.iter().zip(&cpu.memory_bus.cycles).enumerate() {
+        let want = access(cycle);
+        if *actual != want {
+            return Err(format!("M-cycle {n} did {actual:?}, expected {want:?}"));
+        }
+    }
+    Ok(())
+}
+
+/* Decodes a `[address, value, "rwm"]` cycle entry. Internal cycles are
+ * `---` and may or may not carry an address, which is ignored.
+ */
+fn access(cycle: &Value) -> Option<Access> {
+    let pins = cycle[2].as_str().unwrap_or("---");
+    if pins.contains('r') {
+        Some(Access::Read(num(&cycle[0]) as u16, num(&cycle[1]) as u8))
+    } else if pins.contains('w') {
+        Some(Access::Write(num(&cycle[0]) as u16, num(&cycle[1]) as u8))
+    } else {
+        None
+    }
+}
+
+fn num(value: &Value) -> u64 {
+    value.as_u64().unwrap_or_else(|| panic!("expected a number, got {value}"))
+}
+
+fn ram(state: &Value) -> Vec<(u16, u8)> {
+    state["ram"]
+        .as_array()
+        .map(|entries| {
+            entries
+                .iter()
+                .map(|entry| (num(&entry[0]) as u16, num(&entry[1]) as u8))
+                .collect()
+        })
+        .unwrap_or_default()
+}